@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crypto_bigint::U256;
+use elliptic_curve::elliptic_curves_bigint::{Coords, Point};
+use elliptic_curve::field_ext::PowU32;
+use elliptic_curve::secp256k1::SECP256K1;
+
+// The by-value group law that Add used before it went through add_ref:
+// every operand is copied out of the points, x^2 is computed three times
+// when doubling, and each result re-runs the on-curve check in Point::new.
+// Benchmarked next to Add so the two can be compared on the same machine.
+fn copying_add(lhs: Point<4>, rhs: Point<4>) -> Point<4> {
+    assert_eq!(lhs.curve, rhs.curve);
+    let a = lhs.curve.a;
+    match (lhs.coords, rhs.coords) {
+        (Coords::Identity, _) => rhs,
+        (_, Coords::Identity) => lhs,
+        (Coords::Some(x1, y1), Coords::Some(x2, y2)) => {
+            if x1 != x2 {
+                let s = (y2 - y1) / (x2 - x1);
+                let x3 = s.pow_u32(2) - x1 - x2;
+                let y3 = s * (x1 - x3) - y1;
+                Point::new(Coords::Some(x3, y3), lhs.curve)
+            } else if y1 == y2 {
+                let s = (x1.pow_u32(2) + x1.pow_u32(2) + x1.pow_u32(2) + a) / (y1 + y1);
+                let x3 = s.pow_u32(2) - x1 - x1;
+                let y3 = s * (x1 - x3) - y1;
+                Point::new(Coords::Some(x3, y3), lhs.curve)
+            } else {
+                Point::new(Coords::Identity, lhs.curve)
+            }
+        }
+    }
+}
+
+fn point_add(c: &mut Criterion) {
+    let secp256k1 = SECP256K1::new();
+    let g = secp256k1.get_generator_point();
+    let g2 = g + g;
+    assert_eq!(copying_add(g, g2), g + g2);
+    assert_eq!(copying_add(g2, g2), g2 + g2);
+
+    c.bench_function("secp256k1 add", |b| b.iter(|| black_box(g) + black_box(g2)));
+    c.bench_function("secp256k1 add, copying", |b| b.iter(|| copying_add(black_box(g), black_box(g2))));
+    c.bench_function("secp256k1 double", |b| b.iter(|| black_box(g2) + black_box(g2)));
+    c.bench_function("secp256k1 double, copying", |b| b.iter(|| copying_add(black_box(g2), black_box(g2))));
+    c.bench_function("secp256k1 scalar mul", |b| {
+        let k = secp256k1.get_group_order().wrapping_sub(&U256::ONE);
+        b.iter(|| black_box(k) * black_box(g))
+    });
+}
+
+criterion_group!(benches, point_add);
+criterion_main!(benches);
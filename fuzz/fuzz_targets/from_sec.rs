@@ -0,0 +1,45 @@
+#![no_main]
+
+use elliptic_curve::prelude::{Coords, U256};
+use elliptic_curve::secp256k1::SECP256K1;
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+static SECP256K1_PARAMS: OnceLock<SECP256K1> = OnceLock::new();
+
+// from_sec must never panic, and whatever it accepts must be a point on
+// the curve that encodes back to exactly the same bytes. 32-byte inputs
+// are also lifted as an x-coordinate with point_from_x, whose points must
+// be on the curve with the requested y parity.
+fuzz_target!(|data: &[u8]| {
+    let secp256k1 = SECP256K1_PARAMS.get_or_init(SECP256K1::new);
+    if let Ok(x_bytes) = <[u8; 32]>::try_from(data) {
+        let x = U256::from_be_slice(&x_bytes);
+        for even_y in [true, false] {
+            let Some(point) = secp256k1.point_from_x(x, even_y) else {
+                continue;
+            };
+            assert!(secp256k1.get_curve().contains(&point));
+            match point.coords {
+                Coords::Some(px, py) => {
+                    assert_eq!(px.get_num(), x);
+                    assert_eq!(py.get_num().bit_vartime(0), !even_y);
+                }
+                Coords::Identity => panic!("point_from_x gave the identity"),
+            }
+        }
+    }
+
+    let Ok(point) = secp256k1.from_sec(data) else {
+        return;
+    };
+    assert!(secp256k1.get_curve().contains(&point));
+    assert!(!point.is_identity());
+
+    let encoded = match data.len() {
+        33 => point.to_sec_compressed().unwrap().to_vec(),
+        65 => point.to_sec_uncompressed().unwrap().to_vec(),
+        len => panic!("accepted a {}-byte encoding", len),
+    };
+    assert_eq!(encoded, data);
+});
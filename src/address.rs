@@ -0,0 +1,160 @@
+use crate::elliptic_curves_bigint::Point;
+use crate::encoding::bytes_to_hex;
+use crate::hash::{hash160, keccak256};
+use crate::secp256k1::SECP256K1;
+
+impl SECP256K1 {
+    // keccak256 of the 64-byte x || y (no 0x04 prefix), last 20 bytes, EIP-55
+    // cased. Panics for the identity, which has no x || y encoding.
+    #[must_use]
+    pub fn to_eth_address(&self, pubkey: Point<4>) -> String {
+        let raw: [u8; 64] = pubkey.try_into().expect("the identity point has no address");
+
+        let digest = keccak256(raw);
+        let address = bytes_to_hex(&digest[12..]);
+
+        // EIP-55: uppercase a letter when the matching nibble of keccak(address) is >= 8
+        let checksum = keccak256(address.as_bytes());
+        let cased: String = address
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let nibble = (checksum[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+                if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+            })
+            .collect();
+
+        format!("0x{}", cased)
+    }
+
+    // ripemd160(sha256(SEC1 key)), the 20 bytes inside P2PKH and P2WPKH
+    // addresses and scripts; compressed picks the 33- or 65-byte encoding.
+    // Panics for the identity, which has no SEC1 encoding.
+    #[must_use]
+    pub fn pubkey_hash160(&self, pubkey: Point<4>, compressed: bool) -> [u8; 20] {
+        let sec = if compressed {
+            pubkey.to_sec_compressed().map(|sec| sec.to_vec())
+        } else {
+            pubkey.to_sec_uncompressed().map(|sec| sec.to_vec())
+        };
+        hash160(sec.expect("the identity point has no pubkey hash"))
+    }
+
+    // BIP 173 P2WPKH address: witness version 0 and the compressed key's
+    // hash160, bech32 encoded under hrp ("bc" for mainnet, "tb" for testnet)
+    #[must_use]
+    pub fn to_bech32_address(&self, pubkey: Point<4>, hrp: &str) -> String {
+        let mut data = vec![0u8];
+        data.extend(to_base32(&self.pubkey_hash160(pubkey, true)));
+        bech32_encode(hrp, &data)
+    }
+}
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+// the BCH code checksum of BIP 173, over 5-bit values
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk = 1u32;
+    for &value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ff_ffff) << 5) ^ u32::from(value);
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+// bytes regrouped into 5-bit values, zero padding the last one
+fn to_base32(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut acc, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        acc = (acc << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(((acc >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        out.push(((acc << (5 - bits)) & 31) as u8);
+    }
+    out
+}
+
+// hrp || "1" || data || checksum, for 5-bit data and a lowercase hrp
+fn bech32_encode(hrp: &str, data: &[u8]) -> String {
+    let mut values: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|c| c & 31));
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; 6]);
+    let checksum = bech32_polymod(&values) ^ 1;
+
+    let mut address = format!("{}1", hrp);
+    address.extend(data.iter().map(|&d| BECH32_CHARSET[d as usize] as char));
+    address.extend((0..6).map(|i| BECH32_CHARSET[((checksum >> (5 * (5 - i))) & 31) as usize] as char));
+    address
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_bigint::U256;
+
+    #[test]
+    fn eth_address_works() {
+        let secp256k1 = SECP256K1::new();
+
+        let public = secp256k1.get_public_key(U256::from(1u8));
+        assert_eq!(secp256k1.to_eth_address(public), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+        let public = secp256k1.get_public_key(U256::from(2u8));
+        assert_eq!(secp256k1.to_eth_address(public), "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF");
+    }
+
+    #[test]
+    #[should_panic]
+    fn eth_address_rejects_the_identity() {
+        let secp256k1 = SECP256K1::new();
+        let _ = secp256k1.to_eth_address(Point::new(crate::elliptic_curves_bigint::Coords::Identity, secp256k1.get_curve()));
+    }
+
+    #[test]
+    fn pubkey_hash160_works() {
+        // secret key 1, behind 1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH and
+        // 1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm
+        let secp256k1 = SECP256K1::new();
+        let public = secp256k1.get_public_key(U256::from(1u8));
+        assert_eq!(bytes_to_hex(&secp256k1.pubkey_hash160(public, true)), "751e76e8199196d454941c45d1b3a323f1433bd6");
+        assert_eq!(bytes_to_hex(&secp256k1.pubkey_hash160(public, false)), "91b24bf9f5288532960ac687abb035127b1d28a5");
+    }
+
+    #[test]
+    #[should_panic]
+    fn pubkey_hash160_rejects_the_identity() {
+        let secp256k1 = SECP256K1::new();
+        let _ = secp256k1.pubkey_hash160(Point::new(crate::elliptic_curves_bigint::Coords::Identity, secp256k1.get_curve()), true);
+    }
+
+    #[test]
+    fn bech32_address_works() {
+        // BIP 173's example P2WPKH addresses, for secret key 1
+        let secp256k1 = SECP256K1::new();
+        let public = secp256k1.get_public_key(U256::from(1u8));
+        assert_eq!(secp256k1.to_bech32_address(public, "bc"), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert_eq!(secp256k1.to_bech32_address(public, "tb"), "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx");
+    }
+
+    #[test]
+    fn bech32_checksum_works() {
+        // "a12uel5l" is the shortest valid string in the BIP 173 test vectors
+        assert_eq!(bech32_encode("a", &[]), "a12uel5l");
+        assert_eq!(to_base32(&[0xff]), [31u8, 28]);
+        assert_eq!(to_base32(&[]), [0u8; 0]);
+    }
+}
@@ -0,0 +1,294 @@
+use finite_field::FieldElementBig;
+use crate::elliptic_curves_bigint::{Coords, EllipticCurve, Point};
+use crate::error::{EcError, UnknownCurve};
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::{Uint, U256};
+use num::Zero;
+use num_bigint::BigUint;
+use Coords::Some;
+
+// a curve together with its base point and the order of that point
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CurveParams<const LIMBS: usize> {
+    pub curve: EllipticCurve<LIMBS>,
+    pub generator: Point<LIMBS>,
+    pub order: Uint<LIMBS>,
+}
+
+// Any short Weierstrass curve y^2 = x^3 + ax + b over a prime that fits
+// in LIMBS words. The modulus lives in the field elements at runtime, so
+// the usual Point / Add / Mul code works for every size.
+pub type DynCurve<const LIMBS: usize> = CurveParams<LIMBS>;
+
+impl<const LIMBS: usize> CurveParams<LIMBS> {
+    // builds the curve from raw parameters, checking G is on it and that
+    // the order leaves room for a nonzero scalar below it
+    pub fn new(
+        p: Uint<LIMBS>,
+        a: Uint<LIMBS>,
+        b: Uint<LIMBS>,
+        gx: Uint<LIMBS>,
+        gy: Uint<LIMBS>,
+        order: Uint<LIMBS>,
+    ) -> Result<CurveParams<LIMBS>, EcError> {
+        if order < Uint::from(2u8) {
+            return Err(EcError::InvalidParameters);
+        }
+        let curve = EllipticCurve::new(FieldElementBig::new(a, p), FieldElementBig::new(b, p));
+        let generator = Point::try_new(Some(FieldElementBig::new(gx, p), FieldElementBig::new(gy, p)), curve)?;
+        Ok(CurveParams {
+            curve,
+            generator,
+            order,
+        })
+    }
+
+    // scalar * point for a point in the subgroup generated by the
+    // generator. The scalar is reduced mod order first, so the loop runs
+    // over at most bits(order) bits however wide the scalar is.
+    pub fn mul_reduced(&self, scalar: Uint<LIMBS>, point: Point<LIMBS>) -> Point<LIMBS> {
+        scalar.wrapping_rem(&self.order) * point
+    }
+
+    // On the curve and killed by the order, i.e. in the subgroup the
+    // generator spans. With cofactor 1 (secp256k1, P-256) every curve point
+    // passes the second check; with a larger cofactor it rejects points of
+    // small order and their sums with subgroup points.
+    pub fn is_in_subgroup(&self, point: &Point<LIMBS>) -> bool {
+        self.curve.contains(point) && (self.order * *point).is_identity()
+    }
+
+    // Hasse: #E lies within 2 sqrt(p) of p + 1. The order is the
+    // generator's, which divides #E, so this accepts it when some multiple
+    // h * order lands in that interval. It catches grossly wrong order
+    // constants, not every wrong one.
+    pub fn hasse_ok(&self) -> bool {
+        let p = to_biguint(&self.curve.a.get_prime());
+        let order = to_biguint(&self.order);
+        if order.is_zero() {
+            return false;
+        }
+        // |#E - (p + 1)| <= 2 sqrt(p), and #E is an integer, so the bound
+        // can be floor(sqrt(4p))
+        let t = (&p * 4u8).sqrt();
+        let low = &p + 1u8 - &t;
+        let high = &p + 1u8 + &t;
+        &high / &order * &order >= low
+    }
+}
+
+fn to_biguint<const LIMBS: usize>(value: &Uint<LIMBS>) -> BigUint {
+    let bytes: Vec<u8> = value.as_words().iter().flat_map(|word| word.to_le_bytes()).collect();
+    BigUint::from_bytes_le(&bytes)
+}
+
+impl EllipticCurve<4> {
+    // looks up a named curve by its SEC/X9.62 name or its dotted OID
+    pub fn from_name(name: &str) -> Result<CurveParams<4>, UnknownCurve> {
+        match name {
+            "secp256k1" | "1.3.132.0.10" => {
+                let secp256k1 = SECP256K1::new();
+                Ok(CurveParams {
+                    curve: secp256k1.get_curve(),
+                    generator: secp256k1.get_generator_point(),
+                    order: secp256k1.get_group_order(),
+                })
+            }
+            "prime256v1" | "secp256r1" | "P-256" | "1.2.840.10045.3.1.7" => Ok(p256()),
+            _ => Err(UnknownCurve(name.to_owned())),
+        }
+    }
+}
+
+// NIST P-256, y^2 = x^3 - 3x + b
+fn p256() -> CurveParams<4> {
+    let p = U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    let a = U256::from_be_hex("ffffffff00000001000000000000000000000000fffffffffffffffffffffffc");
+    let b = U256::from_be_hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b");
+    let gx = U256::from_be_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+    let gy = U256::from_be_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+    let n = U256::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+
+    let curve = EllipticCurve::new(FieldElementBig::new(a, p), FieldElementBig::new(b, p));
+    let generator = Point::new(Some(FieldElementBig::new(gx, p), FieldElementBig::new(gy, p)), curve);
+    CurveParams {
+        curve,
+        generator,
+        order: n,
+    }
+}
+
+// y^2 = x^3 + 7 over F_223 with G = (47, 71) of order 21, in any width;
+// #E = 252 = 12 * 21
+#[cfg(test)]
+pub(crate) fn toy_params<const LIMBS: usize>() -> DynCurve<LIMBS> {
+    CurveParams::new(
+        Uint::from(223u8),
+        Uint::ZERO,
+        Uint::from(7u8),
+        Uint::from(47u8),
+        Uint::from(71u8),
+        Uint::from(21u8),
+    ).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_bigint::{U128, U384, U4096};
+
+    #[test]
+    fn from_name_works() {
+        let secp256k1 = SECP256K1::new();
+        let params = EllipticCurve::from_name("secp256k1").unwrap();
+        assert_eq!(params.generator, secp256k1.get_generator_point());
+        assert_eq!(EllipticCurve::from_name("1.3.132.0.10"), Ok(params));
+
+        let params = EllipticCurve::from_name("prime256v1").unwrap();
+        assert!(params.curve.contains(&params.generator));
+        assert!((params.order * params.generator).is_identity());
+        assert_eq!(EllipticCurve::from_name("1.2.840.10045.3.1.7"), Ok(params));
+
+        assert_eq!(EllipticCurve::from_name("secp256k2"), Err(UnknownCurve("secp256k2".to_owned())));
+    }
+
+    #[test]
+        // the toy curve in U128
+        // y^2 = x^3 + 7 over F_223 in U128, with G = (47, 71) of order 21
+        let params: DynCurve<2> = toy_params();
+        assert!(params.curve.contains(&params.generator));
+        assert!((params.order * params.generator).is_identity());
+        assert!(!(U128::from(20u8) * params.generator).is_identity());
+
+        let bad = CurveParams::new(
+            U128::from(223u8),
+            U128::ZERO,
+            U128::from(7u8),
+            U128::from(47u8),
+            U128::from(72u8),
+            U128::from(21u8),
+        );
+        assert_eq!(bad, Err(EcError::NotOnCurve));
+    }
+
+    #[test]
+    fn new_rejects_an_order_below_two() {
+        // the toy curve with its order swapped; new doesn't check that 2 is G's order
+        let with_order = |order| CurveParams::new(
+            U128::from(223u8),
+            U128::ZERO,
+            U128::from(7u8),
+            U128::from(47u8),
+            U128::from(71u8),
+            order,
+        );
+        assert_eq!(with_order(U128::ZERO), Err(EcError::InvalidParameters));
+        assert_eq!(with_order(U128::ONE), Err(EcError::InvalidParameters));
+        assert!(with_order(U128::from(2u8)).is_ok());
+    }
+
+    #[test]
+    fn is_in_subgroup_works() {
+        // y^2 = x^3 + x + 1 over F_23 has 28 = 4 * 7 points; G = (5, 4) has
+        // order 7, and (0, 1) is outside the subgroup it generates
+        let params: DynCurve<2> = CurveParams::new(
+            U128::from(23u8),
+            U128::ONE,
+            U128::ONE,
+            U128::from(5u8),
+            U128::from(4u8),
+            U128::from(7u8),
+        ).unwrap();
+        let g = params.generator;
+        assert!(params.is_in_subgroup(&g));
+        assert!(params.is_in_subgroup(&(U128::from(3u8) * g)));
+        assert!(params.curve.contains(&(U128::from(7u8) * g)));
+
+        let p = U128::from(23u8);
+        let outsider = Point::new(Some(FieldElementBig::new(U128::ZERO, p), FieldElementBig::new(U128::ONE, p)), params.curve);
+        assert!(!params.is_in_subgroup(&outsider));
+        assert!(!params.is_in_subgroup(&(outsider + g)));
+        // 4 * outsider has cleared the cofactor
+        assert!(params.is_in_subgroup(&(U128::from(4u8) * outsider)));
+
+        let secp256k1 = EllipticCurve::from_name("secp256k1").unwrap();
+        assert!(secp256k1.is_in_subgroup(&secp256k1.generator));
+    }
+
+    #[test]
+    fn hasse_ok_works() {
+        // the F_223 curve has 252 = 12 * 21 points, and 224 +- 29 holds 252
+        let params: DynCurve<2> = toy_params();
+        assert!(params.hasse_ok());
+
+        // 300 and its multiples all miss [195, 253]
+        let wrong = CurveParams { order: U128::from(300u16), ..params };
+        assert!(!wrong.hasse_ok());
+        let zero = CurveParams { order: U128::ZERO, ..params };
+        assert!(!zero.hasse_ok());
+
+        let secp256k1 = EllipticCurve::from_name("secp256k1").unwrap();
+        assert!(secp256k1.hasse_ok());
+        assert!(EllipticCurve::from_name("P-256").unwrap().hasse_ok());
+        // n - 2^200 is far below p + 1 - 2^129, and twice it is far above
+        let too_small = secp256k1.order.wrapping_sub(&U256::ONE.shl_vartime(200));
+        assert!(!CurveParams { order: too_small, ..secp256k1 }.hasse_ok());
+    }
+
+    #[test]
+    // the borrowed Add is the form to use at this size
+    #[allow(clippy::op_ref)]
+    fn large_limbs_work() {
+        // the F_223 curve again, in 64-limb (4096-bit) integers
+        let params: DynCurve<64> = toy_params();
+        let g = params.generator;
+        let g2 = &g + &g;
+        assert_eq!(g2, g.double());
+        assert_eq!(&g2 + &g, U4096::from(3u8) * g);
+        assert!(params.curve.contains(&(&g2 + &g)));
+        assert!((params.order * g).is_identity());
+        assert_eq!(params.mul_reduced(U4096::from(22u8), g), g);
+
+        // the same results as the two-limb curve
+        let small: DynCurve<2> = toy_params();
+        match ((U4096::from(3u8) * g).coords, (U128::from(3u8) * small.generator).coords) {
+            (Some(x, y), Some(small_x, small_y)) => {
+                assert_eq!(x.get_num().resize::<2>(), small_x.get_num());
+                assert_eq!(y.get_num().resize::<2>(), small_y.get_num());
+            }
+            _ => panic!("3G is not the identity"),
+        }
+    }
+
+    #[test]
+    fn mul_reduced_works() {
+        let params: DynCurve<2> = toy_params();
+        let g = params.generator;
+
+        // 2^128 - 1 = 3 mod 21: 2 loop iterations instead of 128
+        assert_eq!(params.mul_reduced(U128::MAX, g), U128::from(3u8) * g);
+        assert_eq!(params.mul_reduced(U128::MAX, g), U128::MAX * g);
+        assert!(params.mul_reduced(U128::from(4200u16), g).is_identity());
+        assert_eq!(params.mul_reduced(U128::from(22u8), g), g);
+
+        // secp256k1's n + 5 reduces to 5
+        let secp256k1 = SECP256K1::new();
+        let params = EllipticCurve::from_name("secp256k1").unwrap();
+        let k = params.order.wrapping_add(&U256::from(5u8));
+        assert_eq!(params.mul_reduced(k, params.generator), secp256k1.get_public_key(U256::from(5u8)));
+    }
+
+    #[test]
+    fn dyn_curve_p384_works() {
+        let p = U384::from_be_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff");
+        let params: DynCurve<6> = CurveParams::new(
+            p,
+            p.wrapping_sub(&U384::from(3u8)),
+            U384::from_be_hex("b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef"),
+            U384::from_be_hex("aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7"),
+            U384::from_be_hex("3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f"),
+            U384::from_be_hex("ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973"),
+        ).unwrap();
+        assert!((params.order * params.generator).is_identity());
+    }
+}
@@ -0,0 +1,205 @@
+use finite_field::FieldElementBig;
+use crate::elliptic_curves_bigint::{Coords, EllipticCurve, Point};
+use crate::error::EcError;
+use crate::jacobian::JacobianPoint;
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::{Encoding, Uint, U256};
+use crypto_bigint::subtle::{Choice, ConditionallySelectable};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+// (X : Z) stands for the x-coordinate X / Z, with Z == 0 the identity. Which
+// of the two points with that x is meant is forgotten, which is all the
+// Montgomery ladder needs.
+#[derive(Debug, Copy, Clone)]
+pub struct XzPoint<const LIMBS: usize> {
+    pub x: FieldElementBig<LIMBS>,
+    pub z: FieldElementBig<LIMBS>,
+}
+
+impl<const LIMBS: usize> Point<LIMBS> {
+    pub fn to_projective_xz(&self) -> XzPoint<LIMBS> {
+        let p = self.curve.a.get_prime();
+        let one = FieldElementBig::new(Uint::ONE, p);
+        match self.coords {
+            Coords::Some(x, _) => XzPoint { x, z: one },
+            Coords::Identity => XzPoint { x: one, z: FieldElementBig::new(Uint::ZERO, p) },
+        }
+    }
+}
+
+impl<const LIMBS: usize> XzPoint<LIMBS> {
+    // Brier-Joye doubling for y^2 = x^3 + ax + b:
+    // X' = (X^2 - aZ^2)^2 - 8bXZ^3, Z' = 4Z(X^3 + aXZ^2 + bZ^3)
+    fn double(&self, curve: &EllipticCurve<LIMBS>) -> XzPoint<LIMBS> {
+        let (a, b) = (curve.a, curve.b);
+        let xx = self.x * self.x;
+        let zz = self.z * self.z;
+        let t = xx - a * zz;
+        let bxzzz = b * self.x * zz * self.z;
+        let bxzzz2 = bxzzz + bxzzz;
+        let x = t * t - (bxzzz2 + bxzzz2) - (bxzzz2 + bxzzz2);
+        let u = self.x * xx + a * self.x * zz + b * zz * self.z;
+        let zu = self.z * u;
+        let zu2 = zu + zu;
+        XzPoint { x, z: zu2 + zu2 }
+    }
+
+    // self + rhs given the x of rhs - self (affine, so Z = 1):
+    // X' = 2(X1Z2 + X2Z1)(X1X2 + aZ1Z2) + 4bZ1^2Z2^2 - xD(X1Z2 - X2Z1)^2
+    // Z' = (X1Z2 - X2Z1)^2
+    fn add_diff(&self, rhs: &XzPoint<LIMBS>, x_diff: FieldElementBig<LIMBS>, curve: &EllipticCurve<LIMBS>) -> XzPoint<LIMBS> {
+        let (a, b) = (curve.a, curve.b);
+        let x1z2 = self.x * rhs.z;
+        let x2z1 = rhs.x * self.z;
+        let z1z2 = self.z * rhs.z;
+        let s = (x1z2 + x2z1) * (self.x * rhs.x + a * z1z2);
+        let bzz = b * z1z2 * z1z2;
+        let bzz2 = bzz + bzz;
+        let d = x1z2 - x2z1;
+        let dd = d * d;
+        XzPoint { x: s + s + bzz2 + bzz2 - x_diff * dd, z: dd }
+    }
+
+    fn conditional_swap(a: &mut XzPoint<LIMBS>, b: &mut XzPoint<LIMBS>, choice: Choice) {
+        let p = a.x.get_prime();
+        let (mut ax, mut az) = (a.x.get_num(), a.z.get_num());
+        let (mut bx, mut bz) = (b.x.get_num(), b.z.get_num());
+        Uint::conditional_swap(&mut ax, &mut bx, choice);
+        Uint::conditional_swap(&mut az, &mut bz, choice);
+        *a = XzPoint { x: FieldElementBig::new(ax, p), z: FieldElementBig::new(az, p) };
+        *b = XzPoint { x: FieldElementBig::new(bx, p), z: FieldElementBig::new(bz, p) };
+    }
+}
+
+impl SECP256K1 {
+    // the raw shared point secret * their_pub; both sides get the same one
+    #[must_use]
+    pub fn ecdh(&self, secret: U256, their_pub: Point<4>) -> Point<4> {
+        JacobianPoint::mul_affine(secret, &their_pub)
+    }
+
+    // Diagnostic: do the two key pairs agree on the shared point? Each
+    // side's product must also not be the identity.
+    #[must_use]
+    pub fn verify_ecdh(&self, my_secret: U256, their_public: Point<4>, their_secret: U256, my_public: Point<4>) -> bool {
+        let mine = self.ecdh(my_secret, their_public);
+        !mine.is_identity() && mine == self.ecdh(their_secret, my_public)
+    }
+
+    // x(secret * P) from x(P) alone, big-endian, with a Montgomery ladder on
+    // (X : Z). Every bit of the full 256 does one add and one double, and the
+    // pair is swapped without branching on the secret. Errors if their_x is
+    // not on the curve (the ladder would then run on the twist) or the
+    // result is the identity.
+    pub fn ecdh_xonly(&self, secret: U256, their_x: [u8; 32]) -> Result<[u8; 32], EcError> {
+        let x = U256::from_be_bytes(their_x);
+        let their_pub = self.point_from_x(x, true).ok_or(EcError::NotOnCurve)?;
+        let curve = self.get_curve();
+        let x_diff = FieldElementBig::new(x, self.get_order());
+
+        // R1 - R0 = P throughout
+        let mut r0 = Point::new(Coords::Identity, curve).to_projective_xz();
+        let mut r1 = their_pub.to_projective_xz();
+        for i in (0..U256::BITS).rev() {
+            let bit = Choice::from(secret.bit_vartime(i) as u8);
+            XzPoint::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add_diff(&r1, x_diff, &curve);
+            r0 = r0.double(&curve);
+            XzPoint::conditional_swap(&mut r0, &mut r1, bit);
+        }
+
+        if r0.z.get_num() == U256::ZERO {
+            return Err(EcError::Identity);
+        }
+        Ok((r0.x / r0.z).get_num().to_be_bytes())
+    }
+
+    // HKDF-SHA256 (no salt) over the compressed shared point, so the result
+    // can be used directly as symmetric key material. Errors if the shared
+    // point is the identity (a zero secret or an identity public key);
+    // panics if out_len is over HKDF's 255 * 32 bytes.
+    pub fn ecdh_hkdf(&self, secret: U256, their_pub: Point<4>, info: &[u8], out_len: usize) -> Result<Vec<u8>, EcError> {
+        let shared = self.ecdh(secret, their_pub).to_sec_compressed()?;
+        let mut okm = vec![0u8; out_len];
+        Hkdf::<Sha256>::new(None, &shared)
+            .expand(info, &mut okm)
+            .expect("out_len is at most 255 * 32");
+        Ok(okm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ecdh_hkdf_works() {
+        let secp256k1 = SECP256K1::new();
+        let alice = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let bob = U256::from_be_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        let alice_pub = secp256k1.get_public_key(alice);
+        let bob_pub = secp256k1.get_public_key(bob);
+
+        // both parties derive the same bytes, every time
+        let key = secp256k1.ecdh_hkdf(alice, bob_pub, b"session key", 32).unwrap();
+        assert_eq!(key.len(), 32);
+        assert_eq!(secp256k1.ecdh_hkdf(bob, alice_pub, b"session key", 32), Ok(key.clone()));
+        assert_eq!(secp256k1.ecdh_hkdf(alice, bob_pub, b"session key", 32), Ok(key.clone()));
+
+        // info separates keys, and a shorter output is a prefix of a longer one
+        assert_ne!(secp256k1.ecdh_hkdf(alice, bob_pub, b"other", 32), Ok(key.clone()));
+        assert_eq!(secp256k1.ecdh_hkdf(alice, bob_pub, b"session key", 16).unwrap(), key[..16]);
+        assert_eq!(secp256k1.ecdh_hkdf(alice, bob_pub, b"session key", 80).unwrap()[..32], key[..]);
+
+        // no key material from the identity
+        let zero = Point::new(Coords::Identity, secp256k1.get_curve());
+        assert_eq!(secp256k1.ecdh_hkdf(U256::ZERO, bob_pub, b"session key", 32), Err(EcError::Identity));
+        assert_eq!(secp256k1.ecdh_hkdf(alice, zero, b"session key", 32), Err(EcError::Identity));
+
+        assert_eq!(secp256k1.ecdh(alice, bob_pub), secp256k1.ecdh(bob, alice_pub));
+    }
+
+    #[test]
+    fn verify_ecdh_works() {
+        let secp256k1 = SECP256K1::new();
+        let alice = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let bob = U256::from_be_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        let alice_pub = secp256k1.get_public_key(alice);
+        let bob_pub = secp256k1.get_public_key(bob);
+
+        assert!(secp256k1.verify_ecdh(alice, bob_pub, bob, alice_pub));
+        assert!(secp256k1.verify_ecdh(bob, alice_pub, alice, bob_pub));
+
+        // bob's public key paired with the wrong secret
+        assert!(!secp256k1.verify_ecdh(alice, bob_pub, bob.wrapping_add(&U256::ONE), alice_pub));
+        assert!(!secp256k1.verify_ecdh(alice, alice_pub, bob, bob_pub));
+        // zero secrets agree on the identity, which isn't a shared secret
+        assert!(!secp256k1.verify_ecdh(U256::ZERO, bob_pub, U256::ZERO, alice_pub));
+    }
+
+    #[test]
+    fn ecdh_xonly_matches_full_ecdh() {
+        let secp256k1 = SECP256K1::new();
+        let bob_pub = secp256k1.get_public_key(U256::from_be_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"));
+        let bob_x: [u8; 32] = bob_pub.to_bytes_be().unwrap()[..32].try_into().unwrap();
+
+        for secret in [
+            U256::ONE,
+            U256::from(2u8),
+            U256::from(3u8),
+            U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb"),
+            secp256k1.get_group_order().wrapping_sub(&U256::ONE),
+        ] {
+            let full = secp256k1.ecdh(secret, bob_pub).to_bytes_be().unwrap();
+            assert_eq!(secp256k1.ecdh_xonly(secret, bob_x).unwrap()[..], full[..32]);
+        }
+
+        // x = 5 is not on the curve, and n P is the identity
+        let mut five = [0u8; 32];
+        five[31] = 5;
+        assert_eq!(secp256k1.ecdh_xonly(U256::ONE, five), Err(EcError::NotOnCurve));
+        assert_eq!(secp256k1.ecdh_xonly(secp256k1.get_group_order(), bob_x), Err(EcError::Identity));
+        assert_eq!(secp256k1.ecdh_xonly(U256::ZERO, bob_x), Err(EcError::Identity));
+    }
+}
@@ -0,0 +1,873 @@
+use crate::elliptic_curves_bigint::{Coords, Point};
+use crate::encoding::hex_to_bytes;
+use crate::error::{DerError, EcError, ParseError};
+use crate::hash::MessageHasher;
+use crate::jacobian::JacobianPoint;
+use crate::scalar256::Scalar256;
+use crate::secp256k1::SECP256K1;
+use crate::window::WindowTable;
+use crypto_bigint::{CheckedAdd, Encoding, U256};
+use crypto_bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+// An ECDSA signature. The free functions and SECP256K1 methods take the
+// plain (r, s) tuple; this converts to and from it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Signature {
+    pub fn new(r: U256, s: U256) -> Signature {
+        Signature { r, s }
+    }
+
+    // replaces s by n - s when s > n / 2, returning whether it did; (r, s)
+    // and (r, n - s) verify against the same key and hash
+    pub fn normalize_s(&mut self, curve: &SECP256K1) -> bool {
+        if curve.is_low_s(self.s) {
+            return false;
+        }
+        self.s = curve.get_group_order().wrapping_sub(&self.s);
+        true
+    }
+}
+
+impl Signature {
+    // Hex of either DER or the 64-byte compact r || s. A leading 0x30 whose
+    // length byte covers the rest of the input means DER, unless it is 64
+    // bytes that don't decode as DER: then it's a compact signature whose r
+    // happens to start that way.
+    pub fn from_hex(hex: &str) -> Result<Signature, ParseError> {
+        let bytes = hex_to_bytes(hex)?;
+        let looks_like_der = bytes.len() >= 2 && bytes[0] == 0x30 && bytes[1] as usize + 2 == bytes.len();
+        if looks_like_der {
+            match signature_from_der(&bytes) {
+                Ok(sig) => return Ok(sig.into()),
+                Err(err) if bytes.len() != 64 => return Err(ParseError::InvalidDer(err)),
+                Err(_) => {}
+            }
+        }
+        let compact: &[u8; 64] = bytes.as_slice().try_into().map_err(|_| ParseError::InvalidLength)?;
+        Ok(signature_from_compact(compact).into())
+    }
+}
+
+impl TryFrom<&str> for Signature {
+    type Error = ParseError;
+    fn try_from(hex: &str) -> Result<Signature, ParseError> {
+        Signature::from_hex(hex)
+    }
+}
+
+impl From<(U256, U256)> for Signature {
+    fn from((r, s): (U256, U256)) -> Signature {
+        Signature { r, s }
+    }
+}
+
+impl From<Signature> for (U256, U256) {
+    fn from(sig: Signature) -> (U256, U256) {
+        (sig.r, sig.s)
+    }
+}
+
+// Signs SHA-256(message) where the message arrives in chunks, so it never
+// has to be held in memory at once.
+pub struct Signer<'a> {
+    curve: &'a SECP256K1,
+    hasher: Sha256,
+}
+
+impl<'a> Signer<'a> {
+    pub fn new(curve: &'a SECP256K1) -> Signer<'a> {
+        Signer {
+            curve,
+            hasher: Sha256::new(),
+        }
+    }
+
+    pub fn update(&mut self, chunk: impl AsRef<[u8]>) {
+        self.hasher.update(chunk);
+    }
+
+    #[must_use]
+    pub fn finalize(self, secret: U256) -> (U256, U256) {
+        let digest: [u8; 32] = self.hasher.finalize().into();
+        self.curve.sign(secret, U256::from_be_bytes(digest))
+    }
+}
+
+// the fixed 64-byte r || s form (libsecp256k1's "compact"), each half big-endian
+pub fn signature_to_compact(r: U256, s: U256) -> [u8; 64] {
+    let mut compact = [0u8; 64];
+    compact[..32].copy_from_slice(&r.to_be_bytes());
+    compact[32..].copy_from_slice(&s.to_be_bytes());
+    compact
+}
+
+pub fn signature_from_compact(compact: &[u8; 64]) -> (U256, U256) {
+    let r = U256::from_be_slice(&compact[..32]);
+    let s = U256::from_be_slice(&compact[32..]);
+    (r, s)
+}
+
+// DER: 0x30 len 0x02 len(r) r 0x02 len(s) s, with minimal positive integers
+pub fn signature_to_der(r: U256, s: U256) -> Vec<u8> {
+    let r = der_integer(r);
+    let s = der_integer(s);
+    let mut der = vec![0x30, (r.len() + s.len()) as u8];
+    der.extend(r);
+    der.extend(s);
+    der
+}
+
+fn der_integer(value: U256) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(31);
+    let mut int = vec![0x02];
+    // a leading 0x00 keeps a value with the top bit set positive
+    if bytes[start] & 0x80 != 0 {
+        int.push((33 - start) as u8);
+        int.push(0);
+    } else {
+        int.push((32 - start) as u8);
+    }
+    int.extend_from_slice(&bytes[start..]);
+    int
+}
+
+// strict DER decoding: short-form lengths only and no trailing bytes
+pub fn signature_from_der(der: &[u8]) -> Result<(U256, U256), DerError> {
+    if der.len() < 2 {
+        return Err(DerError::Truncated);
+    }
+    if der[0] != 0x30 {
+        return Err(DerError::InvalidTag);
+    }
+    let len = der[1] as usize;
+    if len >= 0x80 {
+        return Err(DerError::InvalidLength);
+    }
+    if der.len() < 2 + len {
+        return Err(DerError::Truncated);
+    }
+    if der.len() > 2 + len {
+        return Err(DerError::InvalidLength);
+    }
+    let (r, rest) = read_der_integer(&der[2..])?;
+    let (s, rest) = read_der_integer(rest)?;
+    if !rest.is_empty() {
+        return Err(DerError::InvalidLength);
+    }
+    Ok((r, s))
+}
+
+fn read_der_integer(input: &[u8]) -> Result<(U256, &[u8]), DerError> {
+    if input.len() < 2 {
+        return Err(DerError::Truncated);
+    }
+    if input[0] != 0x02 {
+        return Err(DerError::InvalidTag);
+    }
+    let len = input[1] as usize;
+    if len == 0 || len >= 0x80 {
+        return Err(DerError::InvalidLength);
+    }
+    if input.len() < 2 + len {
+        return Err(DerError::Truncated);
+    }
+    let int = &input[2..2 + len];
+    // negative, or a zero byte that isn't needed for the sign
+    if int[0] & 0x80 != 0 || (len > 1 && int[0] == 0 && int[1] & 0x80 == 0) {
+        return Err(DerError::InvalidInteger);
+    }
+    let int = if int[0] == 0 { &int[1..] } else { int };
+    if int.len() > 32 {
+        return Err(DerError::InvalidInteger);
+    }
+    let mut padded = [0u8; 32];
+    padded[32 - int.len()..].copy_from_slice(int);
+    Ok((U256::from_be_bytes(padded), &input[2 + len..]))
+}
+
+// bits2int from RFC 6979 / SEC1: the leftmost bitlen(n) bits of hash as
+// an integer, which may still be n or more
+fn bits_to_int(hash: &[u8], n: U256) -> U256 {
+    let take = hash.len().min(32);
+    let mut padded = [0u8; 32];
+    padded[32 - take..].copy_from_slice(&hash[..take]);
+    let value = U256::from_be_bytes(padded);
+    let qlen = n.bits_vartime();
+    if take * 8 > qlen {
+        value >> (take * 8 - qlen)
+    } else {
+        value
+    }
+}
+
+// RFC 6979 deterministic nonce with HMAC-SHA256, for a 256-bit n and hash
+pub(crate) fn rfc6979_nonce(secret: U256, message_hash: U256, n: U256) -> U256 {
+    let hmac = |key: &[u8], parts: &[&[u8]]| -> [u8; 32] {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+        for part in parts {
+            mac.update(part);
+        }
+        mac.finalize().into_bytes().into()
+    };
+    let x = secret.to_be_bytes();
+    let h = message_hash.wrapping_rem(&n).to_be_bytes();
+
+    let mut v = [0x01u8; 32];
+    let mut k = [0x00u8; 32];
+    k = hmac(&k, &[&v, &[0x00], &x, &h]);
+    v = hmac(&k, &[&v]);
+    k = hmac(&k, &[&v, &[0x01], &x, &h]);
+    v = hmac(&k, &[&v]);
+    loop {
+        v = hmac(&k, &[&v]);
+        let nonce = U256::from_be_bytes(v);
+        if nonce != U256::ZERO && nonce < n {
+            return nonce;
+        }
+        k = hmac(&k, &[&v, &[0x00]]);
+        v = hmac(&k, &[&v]);
+    }
+}
+
+// The point side of ECDSA over any curve with a 256-bit prime order n,
+// given a table of multiples of its generator. SECP256K1 does the mod-n
+// math with Scalar256; CurveApi, which may be on another curve, with the
+// *_mod_n helpers below.
+
+// the RFC 6979 nonce k with R = k * G, as (k, R.x, R.y)
+pub(crate) fn nonce_point(g_table: &WindowTable<4>, n: U256, secret: U256, message_hash: U256) -> (U256, U256, U256) {
+    let k = rfc6979_nonce(secret, message_hash, n);
+    match g_table.mul_ct(k).coords {
+        Coords::Some(x, y) => (k, x.get_num(), y.get_num()),
+        Coords::Identity => unreachable!("k is in [1, n)"),
+    }
+}
+
+// bit 0 is the parity of R.y, bit 1 is set when R.x overflowed n
+pub(crate) fn recovery_id(x: U256, y: U256, n: U256) -> u8 {
+    y.bit_vartime(0) as u8 | ((x >= n) as u8) << 1
+}
+
+// the low twin of s, i.e. n - s when s > n / 2
+pub(crate) fn low_s(s: U256, n: U256) -> U256 {
+    if s > n >> 1 {
+        n.wrapping_sub(&s)
+    } else {
+        s
+    }
+}
+
+// x(u1 * G + u2 * Q) == r mod n for each key Q. u1 * G is shared between
+// the keys, and u1, u2 are public, so the variable-time lookups are fine.
+pub(crate) fn check_keys(g_table: &WindowTable<4>, n: U256, keys: &[Point<4>], u1: U256, u2: U256, r: U256) -> Vec<bool> {
+    let u1_g = g_table.mul_vartime(u1);
+    keys.iter()
+        .map(|pubkey| {
+            if pubkey.is_identity() {
+                return false;
+            }
+            let total = u1_g + WindowTable::new(pubkey).mul_vartime(u2);
+            match total.coords {
+                Coords::Some(x, _) => x.get_num().wrapping_rem(&n) == r,
+                Coords::Identity => false,
+            }
+        })
+        .collect()
+}
+
+// s = k^-1 (z + r d) mod n for any prime n, with k inverted as k^(n-2),
+// whose operations don't depend on k
+pub(crate) fn signature_s_mod_n(k: U256, message_hash: U256, r: U256, secret: U256, n: U256) -> U256 {
+    let params = DynResidueParams::new(&n);
+    let residue = |value: U256| DynResidue::new(&value, params);
+    let k_inv = residue(k).pow(&n.wrapping_sub(&U256::from(2u8)));
+    (k_inv * (residue(message_hash) + residue(r) * residue(secret))).retrieve()
+}
+
+// (u1, u2) = (z / s, r / s) mod n, or None unless r and s are in [1, n)
+pub(crate) fn verify_scalars_mod_n(message_hash: U256, sig: (U256, U256), n: U256) -> Option<(U256, U256)> {
+    let (r, s) = sig;
+    if r == U256::ZERO || r >= n || s == U256::ZERO || s >= n {
+        return None;
+    }
+    let params = DynResidueParams::new(&n);
+    let residue = |value: U256| DynResidue::new(&value, params);
+    // s is public, and in [1, n) with n prime, so the inverse exists
+    let (w, _) = residue(s).invert();
+    Some(((residue(message_hash) * w).retrieve(), (residue(r) * w).retrieve()))
+}
+
+impl SECP256K1 {
+    // Deterministic ECDSA (RFC 6979). s is returned as computed, high or
+    // low; see sign_recoverable_compact for the canonical low-s form.
+    #[must_use]
+    pub fn sign(&self, secret: U256, message_hash: U256) -> (U256, U256) {
+        let (r, s, _) = self.sign_with_recovery_id(secret, message_hash);
+        (r, s)
+    }
+
+    // (r, s, recid): bit 0 of recid is the parity of R.y, bit 1 is set
+    // when R.x overflowed n
+    fn sign_with_recovery_id(&self, secret: U256, message_hash: U256) -> (U256, U256, u8) {
+        let n = self.get_group_order();
+        let (k, x, y) = nonce_point(self.g_table(), n, secret, message_hash);
+        let r = Scalar256::new(x);
+        // k is secret, so it takes the constant-time inverse
+        let s = Scalar256::new(k).invert_ct() * (Scalar256::new(message_hash) + r * Scalar256::new(secret));
+        (r.value(), s.value(), recovery_id(x, y, n))
+    }
+
+    // r || s || v with low s and v = 27 + recid, as produced by eth_sign
+    #[must_use]
+    pub fn sign_recoverable_compact(&self, secret: U256, message_hash: U256) -> [u8; 65] {
+        let n = self.get_group_order();
+        let (r, mut s, mut recid) = self.sign_with_recovery_id(secret, message_hash);
+        // s -> n - s is the signature of -R, whose y has the other parity
+        if !self.is_low_s(s) {
+            s = n.wrapping_sub(&s);
+            recid ^= 1;
+        }
+        let mut sig = [0u8; 65];
+        sig[..64].copy_from_slice(&signature_to_compact(r, s));
+        sig[64] = 27 + recid;
+        sig
+    }
+
+    // The public key behind a 65-byte recoverable signature, or None if it
+    // is malformed. v may be the raw recid (0..=3) or 27 + recid.
+    #[must_use]
+    pub fn recover_from_compact(&self, sig: &[u8; 65], message_hash: U256) -> Option<Point<4>> {
+        let n = self.get_group_order();
+        let mut compact = [0u8; 64];
+        compact.copy_from_slice(&sig[..64]);
+        let (r, s) = signature_from_compact(&compact);
+        let recid = match sig[64] {
+            v @ 0..=3 => v,
+            v @ 27..=30 => v - 27,
+            _ => return None,
+        };
+        if r == U256::ZERO || r >= n || s == U256::ZERO || s >= n {
+            return None;
+        }
+
+        let x = if recid & 2 == 0 { r } else { Option::from(r.checked_add(&n))? };
+        let big_r = self.point_from_x(x, recid & 1 == 0)?;
+
+        // Q = r^-1 (s R - z G)
+        let r_inv = Scalar256::new(r).invert()?;
+        let u1 = -Scalar256::new(message_hash) * r_inv;
+        let u2 = Scalar256::new(s) * r_inv;
+        let g = self.get_generator_point();
+        let q = JacobianPoint::mul_affine(u1.value(), &g) + JacobianPoint::mul_affine(u2.value(), &big_r);
+        if q.is_identity() {
+            return None;
+        }
+        Some(q)
+    }
+
+    // ECDSA: accept (r, s) when r == x(z/s * G + r/s * Q) mod n
+    #[must_use]
+    pub fn verify(&self, pubkey: Point<4>, message_hash: U256, sig: (U256, U256)) -> bool {
+        self.verify_many_keys(&[pubkey], message_hash, sig)[0]
+    }
+
+    // verify against each candidate key in turn, sharing the work that
+    // depends only on the signature
+    #[must_use]
+    pub fn verify_many_keys(&self, keys: &[Point<4>], message_hash: U256, sig: (U256, U256)) -> Vec<bool> {
+        let n = self.get_group_order();
+        let (r, s) = sig;
+        if r == U256::ZERO || r >= n || s == U256::ZERO || s >= n {
+            return vec![false; keys.len()];
+        }
+        let Some(w) = Scalar256::new(s).invert() else {
+            return vec![false; keys.len()];
+        };
+        let u1 = Scalar256::new(message_hash) * w;
+        let u2 = Scalar256::new(r) * w;
+        check_keys(self.g_table(), n, keys, u1.value(), u2.value(), r)
+    }
+
+    // sign / verify over hasher.hash(msg) rather than a precomputed hash
+    #[must_use]
+    pub fn sign_message(&self, secret: U256, msg: impl AsRef<[u8]>, hasher: &impl MessageHasher) -> (U256, U256) {
+        self.sign(secret, U256::from_be_bytes(hasher.hash(msg.as_ref())))
+    }
+
+    #[must_use]
+    pub fn verify_message(&self, pubkey: Point<4>, msg: impl AsRef<[u8]>, sig: (U256, U256), hasher: &impl MessageHasher) -> bool {
+        self.verify(pubkey, U256::from_be_bytes(hasher.hash(msg.as_ref())), sig)
+    }
+
+    // verify for each (pubkey, message_hash, sig), reporting every result
+    // rather than stopping at the first failure, so a failed batch can be
+    // narrowed down to the bad signatures
+    #[must_use]
+    pub fn verify_batch_report(&self, items: &[(Point<4>, U256, (U256, U256))]) -> Vec<bool> {
+        items.iter().map(|(pubkey, message_hash, sig)| self.verify(*pubkey, *message_hash, *sig)).collect()
+    }
+
+    // verify with the hash as bytes, of any length: ECDSA uses its leftmost
+    // bitlen(n) bits, so SHA-1 digests are taken whole and SHA-512 ones cut
+    // to their first 32 bytes
+    #[must_use]
+    pub fn verify_hash_bytes(&self, pubkey: Point<4>, hash: impl AsRef<[u8]>, sig: (U256, U256)) -> bool {
+        self.verify(pubkey, bits_to_int(hash.as_ref(), self.get_group_order()), sig)
+    }
+
+    // s <= n / 2. Of the twins (r, s) and (r, n - s), which both verify,
+    // exactly one is low, so requiring it makes signatures non-malleable.
+    #[must_use]
+    pub fn is_low_s(&self, s: U256) -> bool {
+        s <= self.get_group_order() >> 1
+    }
+
+    // verify, rejecting the high-s twin as BIP 62 / BIP 146 require
+    #[must_use]
+    pub fn verify_low_s(&self, pubkey: Point<4>, message_hash: U256, sig: (U256, U256)) -> bool {
+        self.is_low_s(sig.1) && self.verify(pubkey, message_hash, sig)
+    }
+
+    // verify, but an invalid public key is an error rather than a plain false
+    pub fn verify_strict(&self, pubkey: Point<4>, message_hash: U256, sig: (U256, U256)) -> Result<bool, EcError> {
+        self.validate_public_key(&pubkey)?;
+        Ok(self.verify(pubkey, message_hash, sig))
+    }
+
+    // rejects malformed DER before any curve arithmetic
+    pub fn verify_der(&self, pubkey: Point<4>, message_hash: U256, der_sig: &[u8]) -> Result<bool, DerError> {
+        let sig = signature_from_der(der_sig)?;
+        Ok(self.verify(pubkey, message_hash, sig))
+    }
+
+    // decompresses a SEC-encoded public key, then verifies
+    pub fn verify_with_pubkey_bytes(&self, pubkey_sec: &[u8], message_hash: U256, sig: (U256, U256)) -> Result<bool, ParseError> {
+        let pubkey = self.from_sec(pubkey_sec)?;
+        Ok(self.verify(pubkey, message_hash, sig))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::bytes_to_hex;
+
+    // textbook ECDSA with a caller-chosen nonce k, to build signatures for the tests
+    fn sign_with_nonce(secp256k1: &SECP256K1, secret: U256, z: U256, k: U256) -> (U256, U256) {
+        let r = match secp256k1.get_public_key(k).coords {
+            Coords::Some(x, _) => Scalar256::new(x.get_num()),
+            Coords::Identity => unreachable!(),
+        };
+        let s = Scalar256::new(k).invert().unwrap() * (Scalar256::new(z) + r * Scalar256::new(secret));
+        (r.value(), s.value())
+    }
+
+    #[test]
+    fn verify_works() {
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let public = secp256k1.get_public_key(secret);
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let k = U256::from_be_hex("0000000000000000000000000000000000000000000000000000000000001234");
+
+        let sig = sign_with_nonce(&secp256k1, secret, z, k);
+        assert!(secp256k1.verify(public, z, sig));
+
+        // a different hash, key or s doesn't verify
+        assert!(!secp256k1.verify(public, z.wrapping_add(&U256::ONE), sig));
+        assert!(!secp256k1.verify(secp256k1.get_generator_point(), z, sig));
+        assert!(!secp256k1.verify(public, z, (sig.0, sig.1.wrapping_add(&U256::ONE))));
+        assert!(!secp256k1.verify(public, z, (U256::ZERO, sig.1)));
+    }
+
+    #[test]
+    fn verify_many_keys_works() {
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from(12345u32);
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = secp256k1.sign(secret, z);
+
+        let candidates: Vec<Point<4>> = [1u32, 12344, 12345, 12346]
+            .iter()
+            .map(|k| secp256k1.get_public_key(U256::from(*k)))
+            .collect();
+        assert_eq!(secp256k1.verify_many_keys(&candidates, z, sig), [false, false, true, false]);
+        for key in &candidates {
+            assert_eq!(secp256k1.verify_many_keys(&[*key], z, sig)[0], secp256k1.verify(*key, z, sig));
+        }
+
+        // a bad signature fails for every key, and no keys give no answers
+        assert_eq!(secp256k1.verify_many_keys(&candidates, z, (U256::ZERO, sig.1)), [false; 4]);
+        assert!(secp256k1.verify_many_keys(&[], z, sig).is_empty());
+    }
+
+    #[test]
+    fn verify_batch_report_works() {
+        let secp256k1 = SECP256K1::new();
+        let items: Vec<(Point<4>, U256, (U256, U256))> = (1u8..=3)
+            .map(|i| {
+                let secret = secp256k1.get_secret_key();
+                let z = U256::from_be_bytes(crate::hash::sha256([i]));
+                (secp256k1.get_public_key(secret), z, secp256k1.sign(secret, z))
+            })
+            .collect();
+        assert_eq!(secp256k1.verify_batch_report(&items), [true, true, true]);
+
+        // the second signature over another hash
+        let mut bad = items.clone();
+        bad[1].1 = bad[1].1.wrapping_add(&U256::ONE);
+        assert_eq!(secp256k1.verify_batch_report(&bad), [true, false, true]);
+        assert!(secp256k1.verify_batch_report(&[]).is_empty());
+    }
+
+    #[test]
+    fn verify_hash_bytes_works() {
+        use sha2::Sha512;
+
+        let secp256k1 = SECP256K1::new();
+        let secret = secp256k1.get_secret_key();
+        let public = secp256k1.get_public_key(secret);
+
+        // 32 bytes: the hash is the integer
+        let hash = crate::hash::sha256(b"abc");
+        let sig = secp256k1.sign(secret, U256::from_be_bytes(hash));
+        assert!(secp256k1.verify_hash_bytes(public, hash, sig));
+        assert!(!secp256k1.verify_hash_bytes(public, &hash[..31], sig));
+
+        // 20 bytes, like SHA-1: shorter than n, so taken whole
+        let short = crate::hash::ripemd160(b"abc");
+        let mut padded = [0u8; 32];
+        padded[12..].copy_from_slice(&short);
+        let sig = secp256k1.sign(secret, U256::from_be_bytes(padded));
+        assert!(secp256k1.verify_hash_bytes(public, short, sig));
+
+        // 64 bytes, like SHA-512: only the first 32 count
+        let long: [u8; 64] = Sha512::digest(b"abc").into();
+        let sig = secp256k1.sign(secret, U256::from_be_slice(&long[..32]));
+        assert!(secp256k1.verify_hash_bytes(public, long, sig));
+        let mut tail_changed = long;
+        tail_changed[63] ^= 1;
+        assert!(secp256k1.verify_hash_bytes(public, tail_changed, sig));
+        let mut head_changed = long;
+        head_changed[0] ^= 1;
+        assert!(!secp256k1.verify_hash_bytes(public, head_changed, sig));
+    }
+
+    #[test]
+    fn bits_to_int_works() {
+        // with a 12-bit n, 16 bits of hash lose their low 4
+        let n = U256::from(0xfffu16);
+        assert_eq!(bits_to_int(&[0xab, 0xcd], n), U256::from(0xabcu16));
+        assert_eq!(bits_to_int(&[0xab], n), U256::from(0xabu8));
+        assert_eq!(bits_to_int(&[], n), U256::ZERO);
+    }
+
+    #[test]
+    fn sign_works() {
+        // private key 1, sha256("Satoshi Nakamoto"): the RFC 6979 nonce is
+        // 8f8a276c..., which gives this r and a high s
+        let secp256k1 = SECP256K1::new();
+        let z = U256::from_be_hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        let (r, s) = secp256k1.sign(U256::ONE, z);
+        assert_eq!(r, U256::from_be_hex("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8"));
+        assert_eq!(s, U256::from_be_hex("dbbd3162d46e9f9bef7feb87c16dc13b4f6568a87f4e83f728e2443ba586675c"));
+        assert!(secp256k1.verify(secp256k1.get_generator_point(), z, (r, s)));
+
+        // deterministic
+        assert_eq!(secp256k1.sign(U256::ONE, z), (r, s));
+    }
+
+    #[test]
+    fn sign_matches_variable_time_inversion() {
+        // the nonce is inverted in constant time; the result must be the
+        // same as with the textbook inverse
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        for secret in [
+            U256::ONE,
+            U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb"),
+            n.wrapping_sub(&U256::ONE),
+        ] {
+            let k = rfc6979_nonce(secret, z, n);
+            assert_eq!(secp256k1.sign(secret, z), sign_with_nonce(&secp256k1, secret, z, k));
+        }
+    }
+
+    #[test]
+    fn sign_message_works() {
+        use crate::hash::{DoubleSha256Hasher, Keccak256Hasher, Sha256Hasher};
+
+        let secp256k1 = SECP256K1::new();
+        let secret = secp256k1.get_secret_key();
+        let public = secp256k1.get_public_key(secret);
+        let msg = b"pay bob 1 btc";
+
+        let sha = secp256k1.sign_message(secret, msg, &Sha256Hasher);
+        let keccak = secp256k1.sign_message(secret, msg, &Keccak256Hasher);
+        let double_sha = secp256k1.sign_message(secret, msg, &DoubleSha256Hasher);
+        assert!(secp256k1.verify_message(public, msg, sha, &Sha256Hasher));
+        assert!(secp256k1.verify_message(public, msg, keccak, &Keccak256Hasher));
+        assert!(secp256k1.verify_message(public, msg, double_sha, &DoubleSha256Hasher));
+
+        // the hasher is part of what's signed
+        assert!(!secp256k1.verify_message(public, msg, sha, &Keccak256Hasher));
+        assert!(!secp256k1.verify_message(public, msg, keccak, &DoubleSha256Hasher));
+        assert!(!secp256k1.verify_message(public, msg, double_sha, &Sha256Hasher));
+
+        // sign_message is sign over the digest
+        let z = U256::from_be_bytes(crate::hash::sha256(msg));
+        assert_eq!(sha, secp256k1.sign(secret, z));
+
+        // any byte container will do for the message
+        let text = String::from("pay bob 1 btc");
+        assert_eq!(secp256k1.sign_message(secret, &text, &Sha256Hasher), sha);
+        assert_eq!(secp256k1.sign_message(secret, text.as_bytes(), &Sha256Hasher), sha);
+        assert!(secp256k1.verify_message(public, "pay bob 1 btc", sha, &Sha256Hasher));
+        assert!(secp256k1.verify_message(public, &msg[..], sha, &Sha256Hasher));
+    }
+
+    #[test]
+    fn streaming_signer_works() {
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let message: Vec<u8> = (0..10_000u32).map(|i| (i * 31 % 251) as u8).collect();
+
+        let z = U256::from_be_bytes(crate::hash::sha256(&message));
+        let one_shot = secp256k1.sign(secret, z);
+
+        for chunk_size in [1, 64, 1000, 9999] {
+            let mut signer = Signer::new(&secp256k1);
+            for chunk in message.chunks(chunk_size) {
+                signer.update(chunk);
+            }
+            assert_eq!(signer.finalize(secret), one_shot);
+        }
+        assert!(secp256k1.verify(secp256k1.get_public_key(secret), z, one_shot));
+    }
+
+    #[test]
+    fn normalize_s_works() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+        let g = secp256k1.get_generator_point();
+        let z = U256::from_be_hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+
+        // the RFC 6979 signature for key 1 and this hash has a high s
+        let mut sig = Signature::from(secp256k1.sign(U256::ONE, z));
+        let high_s = sig.s;
+        assert!(sig.normalize_s(&secp256k1));
+        assert_eq!(sig.s, n.wrapping_sub(&high_s));
+        assert_eq!(sig.s, U256::from_be_hex("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"));
+        assert!(secp256k1.verify(g, z, sig.into()));
+
+        // already low: nothing to do
+        assert!(!sig.normalize_s(&secp256k1));
+        assert_eq!(sig.s, n.wrapping_sub(&high_s));
+    }
+
+    #[test]
+    fn verify_low_s_works() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+        let g = secp256k1.get_generator_point();
+        let z = U256::from_be_hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+
+        // the RFC 6979 signature for key 1 and this hash has a high s
+        let (r, high_s) = secp256k1.sign(U256::ONE, z);
+        let low_s = n.wrapping_sub(&high_s);
+        assert!(!secp256k1.is_low_s(high_s));
+        assert!(secp256k1.is_low_s(low_s));
+
+        // both twins verify, only the low one under verify_low_s
+        assert!(secp256k1.verify(g, z, (r, high_s)));
+        assert!(secp256k1.verify(g, z, (r, low_s)));
+        assert!(!secp256k1.verify_low_s(g, z, (r, high_s)));
+        assert!(secp256k1.verify_low_s(g, z, (r, low_s)));
+
+        // n / 2 itself is low, n / 2 + 1 isn't
+        assert!(secp256k1.is_low_s(n >> 1));
+        assert!(!secp256k1.is_low_s((n >> 1).wrapping_add(&U256::ONE)));
+    }
+
+    #[test]
+    fn signature_from_hex_works() {
+        let secp256k1 = SECP256K1::new();
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = Signature::from(secp256k1.sign(U256::from(12345u16), z));
+
+        let compact = bytes_to_hex(&signature_to_compact(sig.r, sig.s));
+        assert_eq!(compact.len(), 128);
+        assert_eq!(Signature::from_hex(&compact), Ok(sig));
+        assert_eq!(Signature::try_from(compact.as_str()), Ok(sig));
+
+        let der = bytes_to_hex(&signature_to_der(sig.r, sig.s));
+        assert!(der.starts_with("30"));
+        assert_eq!(Signature::from_hex(&der), Ok(sig));
+
+        // a compact signature whose r happens to start with 0x30
+        let r = U256::from_be_hex("30aa000000000000000000000000000000000000000000000000000000000001");
+        let compact = bytes_to_hex(&signature_to_compact(r, U256::ONE));
+        assert_eq!(Signature::from_hex(&compact), Ok(Signature::new(r, U256::ONE)));
+
+        // ... or with 0x30 0x3e, which has exactly DER's framing for 64 bytes
+        let r = U256::from_be_hex("303e000000000000000000000000000000000000000000000000000000000001");
+        let compact = bytes_to_hex(&signature_to_compact(r, U256::ONE));
+        assert_eq!(Signature::from_hex(&compact), Ok(Signature::new(r, U256::ONE)));
+
+        assert_eq!(Signature::from_hex(&der[..der.len() - 2]), Err(ParseError::InvalidLength));
+        assert_eq!(Signature::from_hex("3006020101020180"), Err(ParseError::InvalidDer(DerError::InvalidInteger)));
+        assert_eq!(Signature::from_hex("zz"), Err(ParseError::InvalidHex));
+    }
+
+    #[test]
+    fn recoverable_compact_round_trip() {
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let public = secp256k1.get_public_key(secret);
+        let n = secp256k1.get_group_order();
+
+        for z in [
+            U256::from_be_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"),
+            U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60"),
+            U256::from(1u8),
+        ] {
+            let sig = secp256k1.sign_recoverable_compact(secret, z);
+            let mut compact = [0u8; 64];
+            compact.copy_from_slice(&sig[..64]);
+            let (r, s) = signature_from_compact(&compact);
+
+            assert!(s <= n >> 1);
+            assert!(sig[64] == 27 || sig[64] == 28);
+            assert!(secp256k1.verify(public, z, (r, s)));
+            assert_eq!(secp256k1.recover_from_compact(&sig, z), Some(public));
+
+            // the other parity recovers some other key
+            let mut flipped = sig;
+            flipped[64] ^= 1;
+            assert_ne!(secp256k1.recover_from_compact(&flipped, z), Some(public));
+        }
+
+        // this one has a high s, so normalizing flipped the parity of R.y (1) to 0
+        let z = U256::from_be_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        assert_eq!(secp256k1.sign_recoverable_compact(secret, z)[64], 27);
+
+        let mut bad = secp256k1.sign_recoverable_compact(secret, z);
+        bad[64] = 31;
+        assert_eq!(secp256k1.recover_from_compact(&bad, z), None);
+    }
+
+    #[test]
+    fn verify_strict_works() {
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let public = secp256k1.get_public_key(secret);
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = secp256k1.sign(secret, z);
+
+        assert_eq!(secp256k1.verify_strict(public, z, sig), Ok(true));
+        assert_eq!(secp256k1.verify_strict(public, z.wrapping_add(&U256::ONE), sig), Ok(false));
+
+        // same x as the real key but y + 1, which is off the curve
+        let off_curve = match public.coords {
+            Coords::Some(x, y) => {
+                let one = finite_field::FieldElementBig::new(U256::ONE, secp256k1.get_order());
+                Point { coords: Coords::Some(x, y + one), curve: public.curve }
+            }
+            Coords::Identity => unreachable!(),
+        };
+        assert_eq!(secp256k1.verify_strict(off_curve, z, sig), Err(EcError::NotOnCurve));
+
+        let zero = Point::new(Coords::Identity, secp256k1.get_curve());
+        assert_eq!(secp256k1.verify_strict(zero, z, sig), Err(EcError::Identity));
+    }
+
+    #[test]
+    fn compact_signature_works() {
+        // small values are zero padded to 32 bytes each
+        let compact = signature_to_compact(U256::from(1u8), U256::from(0x0203u16));
+        assert_eq!(compact[31], 1);
+        assert_eq!(&compact[62..], &[2u8, 3]);
+        assert!(compact[..31].iter().chain(&compact[32..62]).all(|b| *b == 0));
+        assert_eq!(signature_from_compact(&compact), (U256::from(1u8), U256::from(0x0203u16)));
+
+        // a real signature survives the round trip and still verifies
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = sign_with_nonce(&secp256k1, secret, z, U256::from(99u8));
+        let compact = signature_to_compact(sig.0, sig.1);
+        assert_eq!(crate::encoding::bytes_to_hex(&compact[..32]), format!("{:x}", sig.0));
+        let parsed = signature_from_compact(&compact);
+        assert_eq!(parsed, sig);
+        assert!(secp256k1.verify(secp256k1.get_public_key(secret), z, parsed));
+
+        // the exact bytes for private key 1 and sha256("Satoshi Nakamoto"):
+        // r || low s
+        let z = U256::from_be_hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        let expected = "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+                        2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5";
+        let sig = secp256k1.sign_recoverable_compact(U256::ONE, z);
+        assert_eq!(bytes_to_hex(&sig[..64]), expected);
+        let (r, s) = secp256k1.sign(U256::ONE, z);
+        assert_eq!(bytes_to_hex(&signature_to_compact(r, low_s(s, secp256k1.get_group_order()))), expected);
+    }
+
+    #[test]
+    fn der_signature_works() {
+        // r needs a 0x00 pad byte, s is a single byte
+        let r = U256::from_be_hex("8000000000000000000000000000000000000000000000000000000000000001");
+        let s = U256::from(5u8);
+        let der = signature_to_der(r, s);
+        assert_eq!(&der[..5], &[0x30u8, 38, 0x02, 33, 0x00]);
+        assert_eq!(&der[der.len() - 3..], &[0x02u8, 1, 5]);
+        assert_eq!(signature_from_der(&der), Ok((r, s)));
+
+        // non-minimal and negative integers are rejected
+        assert_eq!(signature_from_der(&[0x30, 6, 0x02, 1, 1, 0x02, 1, 0x80]), Err(DerError::InvalidInteger));
+        assert_eq!(signature_from_der(&[0x30, 7, 0x02, 2, 0, 1, 0x02, 1, 1]), Err(DerError::InvalidInteger));
+        assert_eq!(signature_from_der(&[0x31, 6, 0x02, 1, 1, 0x02, 1, 1]), Err(DerError::InvalidTag));
+        assert_eq!(signature_from_der(&[0x30, 6, 0x02, 1, 1, 0x02, 1, 1, 0]), Err(DerError::InvalidLength));
+    }
+
+    #[test]
+    fn verify_der_works() {
+        let secp256k1 = SECP256K1::new();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let public = secp256k1.get_public_key(secret);
+        let z = U256::from_be_hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = sign_with_nonce(&secp256k1, secret, z, U256::from(1234u16));
+        let der = signature_to_der(sig.0, sig.1);
+
+        assert_eq!(secp256k1.verify_der(public, z, &der), Ok(true));
+        assert_eq!(secp256k1.verify_der(public, z.wrapping_add(&U256::ONE), &der), Ok(false));
+        assert_eq!(secp256k1.verify_der(public, z, &der[..der.len() - 1]), Err(DerError::Truncated));
+    }
+
+    #[test]
+    fn verify_with_pubkey_bytes_works() {
+        let secp256k1 = SECP256K1::new();
+        let z = U256::from_be_hex("bc4d30c6ae9567ce0d0e279eae1e7aa4f144d4c8a8b1b5ad5b1ac38e2ae3a685");
+        let k = U256::from(7u8);
+
+        // the secret key 1 has public key G, whose compressed form is well known
+        let sig = sign_with_nonce(&secp256k1, U256::ONE, z, k);
+        let sec = hex_to_bytes("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert_eq!(secp256k1.verify_with_pubkey_bytes(&sec, z, sig), Ok(true));
+
+        // -G is a different key
+        let mut odd = sec.clone();
+        odd[0] = 0x03;
+        assert_eq!(secp256k1.verify_with_pubkey_bytes(&odd, z, sig), Ok(false));
+
+        assert_eq!(secp256k1.verify_with_pubkey_bytes(&sec[..20], z, sig), Err(ParseError::InvalidLength));
+    }
+}
@@ -2,6 +2,8 @@ use finite_field::{FieldElement};
 use std::ops::{Add, Div, Mul, Rem, Sub, Shr, BitAnd};
 use num::{One, Zero, Num, Bounded};
 use std::fmt::Debug;
+use crate::field_ext::{FieldNeg, PowU32};
+use crate::error::EcError;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Coords<T> {
@@ -11,6 +13,12 @@ pub enum Coords<T> {
 
 use Coords::{Some, Identity};
 
+impl<T> Coords<T> {
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Identity)
+    }
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct EllipticCurve<T> {
     pub a: FieldElement<T>,
@@ -26,25 +34,97 @@ impl<T> EllipticCurve<T> {
     }     
 }
 
+impl<T> EllipticCurve<T> 
+    where T: Rem<Output = T> + Mul<Output = T> + Copy + Sub<Output = T> + Add<Output = T> + Shr<Output = T>,
+          T: One + Num + PartialOrd + Debug,
+{
+    pub fn is_on_curve(&self, coords: &Coords<T>) -> bool {
+        match coords {
+            Some(x, y) => *y * *y == *x * *x * *x + self.a * *x + self.b,
+            Identity => true,
+        }
+    }
+
+    // on the curve and built on this very curve
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        point.curve == *self && self.is_on_curve(&point.coords)
+    }
+}
+
+impl EllipticCurve<u32> {
+    // #E including the identity: each x contributes 1 + (x^3 + ax + b | p)
+    // points. This walks every x, so it's only meant for toy primes (p < 10^6).
+    pub fn count_points(&self) -> u64 {
+        let p = self.a.get_prime() as u128;
+        let a = self.a.get_num() as u128;
+        let b = self.b.get_num() as u128;
+        let mut count = 1;
+        for x in 0..p {
+            let rhs = (x * x % p * x + a * x + b) % p;
+            count += if rhs == 0 {
+                1
+            } else if mod_pow(rhs, (p - 1) / 2, p) == 1 {
+                2
+            } else {
+                0
+            };
+        }
+        count
+    }
+}
+
+fn mod_pow(base: u128, exp: u128, modulus: u128) -> u128 {
+    let mut result = 1;
+    let mut base = base % modulus;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result
+}
+
+// Equality compares the curve as well as the coordinates, so the
+// identities of two different curves are not equal: each curve has its own
+// group and its own neutral element, and Add refuses to mix them anyway.
+// Use is_identity() to test for the identity of whatever curve.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Point<T> {
     coords: Coords<T>,
     curve : EllipticCurve<T>    
 }
 
+impl<T> Point<T> {
+    pub fn is_identity(&self) -> bool {
+        self.coords.is_identity()
+    }
+}
+
+// read access for the conversions into the bigint backend
+impl<T: Copy> Point<T> {
+    pub(crate) fn coords(&self) -> Coords<T> {
+        self.coords
+    }
+
+    pub(crate) fn curve(&self) -> EllipticCurve<T> {
+        self.curve
+    }
+}
+
 impl<T> Point<T> 
     where T: Rem<Output = T> + Mul<Output = T> + Copy + Sub<Output = T> + Add<Output = T> + Shr<Output = T>,
-          T: One + Num + PartialOrd + Bounded + Debug,
+          T: One + Num + PartialOrd + Debug,
 {
     pub fn new(coords: Coords<T>, curve: EllipticCurve<T>) -> Point<T> {
         let a = curve.a;
         let b = curve.b;    
-        let one: T = One::one();
-        let two = one + one;
-        let three = two + one;
 
+        // plain field products, so no 2 or 3 has to be built as a T
         if let Some(x, y) = coords {
-            assert_eq!(y.pow(two), x.pow(three) + a * x + b);    
+            assert_eq!(y * y, x * x * x + a * x + b);    
         }
 
         Point {
@@ -62,36 +142,135 @@ impl<T> Add for Point<T>
     type Output = Self;    
     fn add(self, rhs: Self) -> Self::Output {
         assert_eq!(self.curve, rhs.curve);  
+        if self.is_identity() {
+            return rhs;
+        }
+        if rhs.is_identity() {
+            return self;
+        }
         let a = self.curve.a;
-        let one: T = One::one();
-        let two = one + one;
-        if let Some(x1, y1) = self.coords {
-            if let Some(x2, y2) = rhs.coords {
-                if x1 != x2 {
-                    let s = (y2 - y1) / (x2 - x1); 
-                    let x3 = s.pow(two) - x1 - x2;
-                    let y3 = s * (x1 -x3) - y1;
-                    return Point::new(Some(x3, y3), self.curve);
-                } else if x1 == x2 && y1 == y2 {
-                    let s = (x1.pow(two) + x1.pow(two) + x1.pow(two) + a) / (y1 +  y1);
-                    let x3 = s.pow(two) - x1 - x1;
-                    let y3 = s * (x1 - x3) - y1;
-                    return Point::new(Some(x3, y3), self.curve);
-                }
-            } else {
-                return self;    
-            } 
-        } else {
-            if let Some(_x2, _y2) = rhs.coords {
-                return rhs;    
-            }    
+        if let (Some(x1, y1), Some(x2, y2)) = (self.coords, rhs.coords) {
+            if x1 != x2 {
+                let s = (y2 - y1) / (x2 - x1); 
+                let x3 = s.pow_u32(2) - x1 - x2;
+                let y3 = s * (x1 -x3) - y1;
+                return Point::new(Some(x3, y3), self.curve);
+            } else if y1 == y2 {
+                let s = (x1.pow_u32(2) + x1.pow_u32(2) + x1.pow_u32(2) + a) / (y1 +  y1);
+                let x3 = s.pow_u32(2) - x1 - x1;
+                let y3 = s * (x1 - x3) - y1;
+                return Point::new(Some(x3, y3), self.curve);
+            }
         }
+        // x1 == x2 and y1 != y2: the points are inverses
         Point::new(Identity, self.curve)
     }    
 }
 
+// Points are Copy, so the borrowed forms just copy into the owned Add
+impl<T> Add<&Point<T>> for &Point<T> 
+    where T: PartialEq,
+          T: PartialOrd + Debug + Sub<Output = T> + Rem<Output = T> + Bounded,
+          T: Zero + Copy + Div<Output = T> + Num + Shr<T, Output = T> + One,
+{
+    type Output = Point<T>;
+    fn add(self, rhs: &Point<T>) -> Point<T> {
+        *self + *rhs
+    }
+}
+
+impl<T> Add<&Point<T>> for Point<T> 
+    where T: PartialEq,
+          T: PartialOrd + Debug + Sub<Output = T> + Rem<Output = T> + Bounded,
+          T: Zero + Copy + Div<Output = T> + Num + Shr<T, Output = T> + One,
+{
+    type Output = Point<T>;
+    fn add(self, rhs: &Point<T>) -> Point<T> {
+        self + *rhs
+    }
+}
+
+impl<T> Add<Point<T>> for &Point<T> 
+    where T: PartialEq,
+          T: PartialOrd + Debug + Sub<Output = T> + Rem<Output = T> + Bounded,
+          T: Zero + Copy + Div<Output = T> + Num + Shr<T, Output = T> + One,
+{
+    type Output = Point<T>;
+    fn add(self, rhs: Point<T>) -> Point<T> {
+        *self + rhs
+    }
+}
+
+impl<T> Point<T> 
+    where T: PartialEq,
+          T: PartialOrd + Debug + Sub<Output = T> + Rem<Output = T> + Bounded,
+          T: Zero + Copy + Div<Output = T> + Num + Shr<T, Output = T> + One,
+{
+    // the least k > 0 with kP = 0, by repeated addition (toy curves only)
+    pub fn order(&self) -> u64 {
+        let mut order = 1;
+        let mut current = *self;
+        while !current.is_identity() {
+            current = current + *self;
+            order += 1;
+        }
+        order
+    }
+
+    // Add without the panic: points of different curves give an error.
+    pub fn checked_add(self, rhs: Self) -> Result<Point<T>, EcError> {
+        if self.curve != rhs.curve {
+            return Err(EcError::CurveMismatch);
+        }
+        Ok(self + rhs)
+    }
+
+    // self - rhs, i.e. self + (x, -y) of rhs, with the same curve check
+    pub fn checked_sub(self, rhs: Self) -> Result<Point<T>, EcError> {
+        if self.curve != rhs.curve {
+            return Err(EcError::CurveMismatch);
+        }
+        let negated = match rhs.coords {
+            Some(x, y) => Point { coords: Some(x, y.neg()), curve: rhs.curve },
+            Identity => rhs,
+        };
+        Ok(self + negated)
+    }
+}
+
+impl EllipticCurve<u16> {
+    // Scans every (x, y) of the field for a point of order n. Handy for
+    // building toy test fixtures; far too slow for anything but tiny p.
+    pub fn generator_of_order(&self, n: u64) -> Option<Point<u16>> {
+        let p = self.a.get_prime();
+        for x in 0..p {
+            for y in 0..p {
+                let coords = Some(FieldElement::new(x, p), FieldElement::new(y, p));
+                if self.is_on_curve(&coords) {
+                    let point = Point::new(coords, *self);
+                    if point.order() == n {
+                        return Option::Some(point);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Scalar<T>(T);
 
+impl<T: Copy> Scalar<T> {
+    pub fn new(value: T) -> Scalar<T> {
+        Scalar(value)
+    }
+
+    pub fn value(&self) -> T {
+        self.0
+    }
+}
+
 impl<T> Mul<Point<T>> for Scalar<T> 
     where T: Shr + Zero,
           T: Rem<Output = T> + Mul<Output = T> + Copy + Sub<Output = T> + Add<Output = T> + Shr<Output = T>,
@@ -105,6 +284,10 @@ impl<T> Mul<Point<T>> for Scalar<T>
         let one: T = One::one();
         assert!(coef >= zero);
 
+        if rhs.is_identity() {
+            return rhs;
+        }
+
         let mut current = rhs;
         let mut result = Point::new(Identity, rhs.curve);
         while coef > zero {
@@ -188,6 +371,206 @@ mod tests {
         assert_eq!(zero + point2, point2);     
     }
 
+    #[test]
+    fn contains_works() {
+        let a: FieldElement<u16> = FieldElement::new(0, 223);
+        let b: FieldElement<u16> = FieldElement::new(7, 223);
+        let curve = EllipticCurve {a, b};
+        let b: FieldElement<u16> = FieldElement::new(5, 223);
+        let other = EllipticCurve {a, b};
+
+        let x: FieldElement<u16> = FieldElement::new(47, 223);
+        let y: FieldElement<u16> = FieldElement::new(71, 223);
+        let point = Point::new(Some(x, y), curve);
+        let zero = Point::new(Identity, curve);
+
+        assert!(curve.is_on_curve(&point.coords));
+        assert!(curve.contains(&point));
+        assert!(curve.contains(&zero));
+
+        // a point built on a different curve is not contained
+        assert!(!other.contains(&point));
+        assert!(!other.contains(&zero));
+    }
+
+    #[test]
+    fn is_identity_works() {
+        let a: FieldElement<u16> = FieldElement::new(0, 223);
+        let b: FieldElement<u16> = FieldElement::new(7, 223);
+        let curve = EllipticCurve {a, b};
+        let x: FieldElement<u16> = FieldElement::new(47, 223);
+        let y: FieldElement<u16> = FieldElement::new(71, 223);
+
+        let point = Point::new(Some(x, y), curve);
+        let zero = Point::new(Identity, curve);
+        assert!(!point.is_identity());
+        assert!(!Some(x, y).is_identity());
+        assert!(zero.is_identity());
+        assert!(Coords::<u16>::Identity.is_identity());
+
+        // 21 (47, 71) = 0
+        assert!((Scalar(21u16) * point).is_identity());
+    }
+
+    #[test]
+    fn checked_add_and_sub_work() {
+        let a: FieldElement<u16> = FieldElement::new(0, 223);
+        let b: FieldElement<u16> = FieldElement::new(7, 223);
+        let curve = EllipticCurve {a, b};
+        let b: FieldElement<u16> = FieldElement::new(5, 223);
+        let other = EllipticCurve {a, b};
+
+        let x: FieldElement<u16> = FieldElement::new(47, 223);
+        let y: FieldElement<u16> = FieldElement::new(71, 223);
+        let point = Point::new(Some(x, y), curve);
+        let zero = Point::new(Identity, curve);
+        let foreign = Point::new(Identity, other);
+
+        // mixing curves errors instead of panicking
+        assert_eq!(point.checked_add(foreign), Err(EcError::CurveMismatch));
+        assert_eq!(point.checked_sub(foreign), Err(EcError::CurveMismatch));
+
+        assert_eq!(point.checked_add(point), Ok(point + point));
+        assert_eq!(point.checked_sub(point), Ok(zero));
+        assert_eq!(point.checked_sub(zero), Ok(point));
+        // (2P) - P = P
+        assert_eq!((point + point).checked_sub(point), Ok(point));
+    }
+
+    #[test]
+    fn count_points_works() {
+        let a: FieldElement<u32> = FieldElement::new(0, 223);
+        let b: FieldElement<u32> = FieldElement::new(7, 223);
+        let curve = EllipticCurve {a, b};
+        let count = curve.count_points();
+        assert_eq!(count, 252);
+
+        // Hasse: |#E - (p + 1)| <= 2 sqrt(p)
+        let p = 223f64;
+        assert!((count as f64 - (p + 1.0)).abs() <= 2.0 * p.sqrt());
+
+        // the order 21 of (47, 71) divides #E
+        assert_eq!(count % 21, 0);
+
+        // y^2 = x^3 + 2x + 2 over F_17 has 19 points
+        let a: FieldElement<u32> = FieldElement::new(2, 17);
+        let b: FieldElement<u32> = FieldElement::new(2, 17);
+        assert_eq!(EllipticCurve {a, b}.count_points(), 19);
+    }
+
+    #[test]
+    fn nonzero_a_curve_works() {
+        // y^2 = x^3 + 2x + 2 over F_17, so doubling uses the + a term
+        let a: FieldElement<u16> = FieldElement::new(2, 17);
+        let b: FieldElement<u16> = FieldElement::new(2, 17);
+        let curve = EllipticCurve {a, b};
+        let f = |n| FieldElement::new(n, 17);
+
+        let point = Point::new(Some(f(5), f(1)), curve);
+
+        // s = (3 * 5^2 + 2) / (2 * 1) = 13, x = 13^2 - 10 = 6, y = 13 (5 - 6) - 1 = 3
+        let double = Point::new(Some(f(6), f(3)), curve);
+        assert_eq!(point + point, double);
+        // dropping the a term would give s = 75 / 2 = 12 and land on (15, 15) instead
+        assert_ne!((point + point).coords, Some(f(15), f(15)));
+
+        // P + 2P = (10, 6)
+        assert_eq!(point + double, Point::new(Some(f(10), f(6)), curve));
+
+        // the group has prime order 19, so (5, 1) generates it
+        assert_eq!(point.order(), 19);
+        assert!((Scalar(19u16) * point).is_identity());
+    }
+
+    #[test]
+    fn generator_of_order_works() {
+        let a: FieldElement<u16> = FieldElement::new(0, 223);
+        let b: FieldElement<u16> = FieldElement::new(7, 223);
+        let curve = EllipticCurve {a, b};
+
+        // (47, 71) has order 21
+        let x: FieldElement<u16> = FieldElement::new(47, 223);
+        let y: FieldElement<u16> = FieldElement::new(71, 223);
+        assert_eq!(Point::new(Some(x, y), curve).order(), 21);
+
+        let generator = curve.generator_of_order(7).unwrap();
+        assert_eq!(generator.order(), 7);
+        assert!((Scalar(7u16) * generator).is_identity());
+
+        // #E = 252 = 2^2 * 3^2 * 7 has no subgroup of order 5
+        assert_eq!(curve.generator_of_order(5), None);
+    }
+
+    #[test]
+    // the borrowed impls are what is under test here
+    #[allow(clippy::op_ref)]
+    fn reference_add_works() {
+        let a: FieldElement<u16> = FieldElement::new(0, 223);
+        let b: FieldElement<u16> = FieldElement::new(7, 223);
+        let curve = EllipticCurve {a, b};
+        let point = |x: u16, y: u16| Point::new(Some(FieldElement::new(x, 223), FieldElement::new(y, 223)), curve);
+
+        // (192, 105) + (17, 56) = (170, 142)
+        let (p, q) = (point(192, 105), point(17, 56));
+        let sum = point(170, 142);
+        assert_eq!(&p + &q, sum);
+        assert_eq!(p + &q, sum);
+        assert_eq!(&p + q, sum);
+
+        // summing a slice without copying each element out
+        let points = [p, q, point(170, 142)];
+        let total = points.iter().fold(Point::new(Identity, curve), |acc, point| acc + point);
+        assert_eq!(total, sum + sum);
+    }
+
+    #[test]
+    fn identities_of_different_curves_differ() {
+        let a: FieldElement<u16> = FieldElement::new(0, 223);
+        let curve = EllipticCurve {a, b: FieldElement::new(7, 223)};
+        let other = EllipticCurve {a, b: FieldElement::new(5, 223)};
+
+        let zero = Point::new(Identity, curve);
+        let other_zero = Point::new(Identity, other);
+        assert_ne!(zero, other_zero);
+        assert!(zero.is_identity() && other_zero.is_identity());
+        assert_eq!(zero, Point::new(Identity, curve));
+    }
+
+    #[test]
+    fn u8_curve_works() {
+        // y^2 = x^3 + 7 over F_13 with u8 elements
+        let a: FieldElement<u8> = FieldElement::new(0, 13);
+        let b: FieldElement<u8> = FieldElement::new(7, 13);
+        let curve = EllipticCurve {a, b};
+
+        let x: FieldElement<u8> = FieldElement::new(7, 13);
+        let y: FieldElement<u8> = FieldElement::new(5, 13);
+        let point = Point::new(Some(x, y), curve);
+        assert!(curve.contains(&point));
+
+        // 2 * (7, 5) = (8, 5)
+        let x: FieldElement<u8> = FieldElement::new(8, 13);
+        let y: FieldElement<u8> = FieldElement::new(5, 13);
+        assert_eq!(point + point, Point::new(Some(x, y), curve));
+    }
+
+    #[test]
+    fn u8_curve_near_the_top_of_the_type() {
+        // y^2 = x^3 + 7 over F_251: coordinates above 200 square to well
+        // past u8::MAX, so the check only passes if the field arithmetic
+        // reduces without overflowing
+        let a: FieldElement<u8> = FieldElement::new(0, 251);
+        let b: FieldElement<u8> = FieldElement::new(7, 251);
+        let curve = EllipticCurve {a, b};
+
+        let point = Point::new(Some(FieldElement::new(201, 251), FieldElement::new(235, 251)), curve);
+        assert!(curve.contains(&point));
+        assert!(!curve.is_on_curve(&Some(FieldElement::new(201, 251), FieldElement::new(250, 251))));
+
+        // 2 * (201, 235) = (145, 194)
+        assert_eq!(point + point, Point::new(Some(FieldElement::new(145, 251), FieldElement::new(194, 251)), curve));
+    }
+
     #[test]
     fn scalar_mul_works() {
         // define a point on an elliptic curve
@@ -1,8 +1,12 @@
 use finite_field::FieldElementBig;
+use std::fmt;
 use std::ops::{Add, Mul};
 use crypto_bigint::Uint;
+use crypto_bigint::subtle::Choice;
+use crate::field_ext::PowU32;
+use crate::error::EcError;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 pub enum Coords<const LIMBS: usize> {
     Some(FieldElementBig<LIMBS>, FieldElementBig<LIMBS>),
     Identity,
@@ -10,18 +14,88 @@ pub enum Coords<const LIMBS: usize> {
 
 use Coords::{Some, Identity};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+impl<const LIMBS: usize> Coords<LIMBS> {
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Identity)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
 pub struct EllipticCurve<const LIMBS: usize>{
     pub a: FieldElementBig<LIMBS>,
     pub b: FieldElementBig<LIMBS>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+// Equality compares the curve as well as the coordinates, so the
+// identities of two different curves are not equal: each curve has its own
+// group and its own neutral element, and Add refuses to mix them anyway.
+// Use is_identity() to test for the identity of whatever curve.
+//
+// A Point holds four field elements (x, y, a, b), each carrying its value
+// and the prime, so it is 8 * LIMBS words: 256 bytes at LIMBS = 4 but 4 KiB
+// for a U4096 curve, and every by-value pass copies all of it. For large
+// LIMBS prefer the borrowed forms (&p + &q, &p + q, double) and
+// JacobianPoint for long computations over passing points around by value.
+#[derive(Copy, Clone, PartialEq)]
 pub struct Point<const LIMBS: usize> {
     pub coords: Coords<LIMBS>,
     pub curve : EllipticCurve<LIMBS>    
 }
 
+// A nonzero prime used by a, b and both coordinates. A field element with
+// modulus 0 (or one from another field) would otherwise reach a division
+// by zero or silently wrong arithmetic deep inside the field code.
+fn shares_prime<const LIMBS: usize>(coords: &Coords<LIMBS>, curve: &EllipticCurve<LIMBS>) -> bool {
+    let p = curve.a.get_prime();
+    if p == Uint::ZERO || curve.b.get_prime() != p {
+        return false;
+    }
+    match coords {
+        Some(x, y) => x.get_prime() == p && y.get_prime() == p,
+        Identity => true,
+    }
+}
+
+// Debug shows field elements and the prime as 0x-prefixed hex without
+// leading zeros, rather than the limb arrays a derive would print
+struct Hex<const LIMBS: usize>(Uint<LIMBS>);
+
+impl<const LIMBS: usize> fmt::Debug for Hex<LIMBS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = format!("{:x}", self.0);
+        let digits = digits.trim_start_matches('0');
+        write!(f, "0x{}", if digits.is_empty() { "0" } else { digits })
+    }
+}
+
+impl<const LIMBS: usize> fmt::Debug for Coords<LIMBS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Some(x, y) => f.debug_tuple("Some").field(&Hex(x.get_num())).field(&Hex(y.get_num())).finish(),
+            Identity => f.write_str("Identity"),
+        }
+    }
+}
+
+impl<const LIMBS: usize> fmt::Debug for EllipticCurve<LIMBS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EllipticCurve")
+            .field("a", &Hex(self.a.get_num()))
+            .field("b", &Hex(self.b.get_num()))
+            .field("p", &Hex(self.a.get_prime()))
+            .finish()
+    }
+}
+
+impl<const LIMBS: usize> fmt::Debug for Point<LIMBS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Point")
+            .field("coords", &self.coords)
+            .field("curve", &self.curve)
+            .finish()
+    }
+}
+
 impl<const LIMBS: usize> EllipticCurve<LIMBS> {
     pub fn new(a: FieldElementBig<LIMBS>, b: FieldElementBig<LIMBS>) -> EllipticCurve<LIMBS> {
         EllipticCurve {
@@ -29,16 +103,40 @@ impl<const LIMBS: usize> EllipticCurve<LIMBS> {
             b
         }
     }  
+
+    pub fn is_on_curve(&self, coords: &Coords<LIMBS>) -> bool {
+        match coords {
+            Some(x, y) => y.pow_u32(2) == x.pow_u32(3) + self.a * *x + self.b,
+            Identity => true,
+        }
+    }
+
+    // on the curve and built on this very curve
+    pub fn contains(&self, point: &Point<LIMBS>) -> bool {
+        point.curve == *self && self.is_on_curve(&point.coords)
+    }
+
+    // The same curve with a and b reduced into [0, p). Coefficients loaded
+    // as raw integers may not be, and then two copies of one curve compare
+    // unequal and Add refuses to mix their points.
+    pub fn normalized(&self) -> EllipticCurve<LIMBS> {
+        let p = self.a.get_prime();
+        EllipticCurve {
+            a: FieldElementBig::new(self.a.get_num().wrapping_rem(&p), p),
+            b: FieldElementBig::new(self.b.get_num().wrapping_rem(&p), p),
+        }
+    }
 }
 
 impl<const LIMBS: usize> Point<LIMBS> {
+    // panics if coords is not on curve; use try_new for untrusted input
+    #[must_use]
     pub fn new(coords: Coords<LIMBS>, curve: EllipticCurve<LIMBS>) -> Point<LIMBS> {
-        let two = Uint::from(2u8);
-        let three = Uint::from(3u8);
+        assert!(shares_prime(&coords, &curve), "coordinates and curve must share a nonzero prime");
         let a = curve.a;
         let b = curve.b;
         if let Some(x, y) = coords {
-            assert_eq!(y.pow(two), x.pow(three) + a * x + b);     
+            assert_eq!(y.pow_u32(2), x.pow_u32(3) + a * x + b);     
         }    
 
         Point {
@@ -46,39 +144,204 @@ impl<const LIMBS: usize> Point<LIMBS> {
             curve    
         }        
     }    
+
+    pub fn try_new(coords: Coords<LIMBS>, curve: EllipticCurve<LIMBS>) -> Result<Point<LIMBS>, EcError> {
+        if !shares_prime(&coords, &curve) {
+            return Err(EcError::InvalidParameters);
+        }
+        if !curve.is_on_curve(&coords) {
+            return Err(EcError::NotOnCurve);
+        }
+        Ok(Point {
+            coords,
+            curve
+        })
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Point<LIMBS>, EcError> {
+        if !shares_prime(&self.coords, &self.curve) || !shares_prime(&rhs.coords, &rhs.curve) {
+            return Err(EcError::InvalidParameters);
+        }
+        if self.curve != rhs.curve {
+            return Err(EcError::CurveMismatch);
+        }
+        Ok(self.add_ref(&rhs))
+    }
+
+    // k * self for a small k, without the caller building a Uint
+    #[must_use]
+    pub fn mul_small(self, k: u64) -> Point<LIMBS> {
+        Uint::from_u64(k) * self
+    }
+
+    // self + rhs, along with which branch of the group law produced it
+    pub fn add_with_flag(self, rhs: Self) -> (Point<LIMBS>, AddOutcome) {
+        let outcome = match (&self.coords, &rhs.coords) {
+            (Identity, _) | (_, Identity) => AddOutcome::IdentityOperand,
+            (Some(x1, _), Some(x2, _)) if x1 != x2 => AddOutcome::Normal,
+            (Some(_, y1), Some(_, y2)) if y1 == y2 => AddOutcome::Doubled,
+            _ => AddOutcome::Inverses,
+        };
+        (self.add_ref(&rhs), outcome)
+    }
 }
 
-impl<const LIMBS: usize> Add for Point<LIMBS> {
-    type Output = Point<LIMBS>;
-    fn add(self, rhs: Self) -> Point<LIMBS> {
+// How add_with_flag got its result. Inverses is the only way two
+// non-identity points sum to the identity, apart from doubling a point
+// with y = 0, which reports Doubled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddOutcome {
+    // the chord through two points with different x
+    Normal,
+    // the tangent at P, for P + P
+    Doubled,
+    // P + (-P), giving the identity
+    Inverses,
+    // either operand was the identity, so the other is returned
+    IdentityOperand,
+}
+
+impl<const LIMBS: usize> Point<LIMBS> {
+    // The group law on borrowed points. Results are built directly rather
+    // than through Point::new, since they are on the curve by construction.
+    pub(crate) fn add_ref(&self, rhs: &Self) -> Point<LIMBS> {
         assert_eq!(self.curve, rhs.curve);
-        let a = self.curve.a;
-        let two: Uint<LIMBS> = Uint::from(2u8);
-        if let Some(x1, y1) = self.coords {
-            if let Some(x2, y2) = rhs.coords {
-                if x1 != x2 {
-                    let s = (y2 - y1) / (x2 - x1); 
-                    let x3 = s.pow(two) - x1 - x2;
-                    let y3 = s * (x1 -x3) - y1;
-                    return Point::new(Some(x3, y3), self.curve);
-                } else if x1 == x2 && y1 == y2 {
-                    let s = (x1.pow(two) + x1.pow(two) + x1.pow(two) + a) / (y1 +  y1);
-                    let x3 = s.pow(two) - x1 - x1;
-                    let y3 = s * (x1 - x3) - y1;
-                    return Point::new(Some(x3, y3), self.curve);
-                }
-            } else {
-                return self;    
-            } 
+        assert!(shares_prime(&self.coords, &self.curve) && shares_prime(&rhs.coords, &rhs.curve),
+            "coordinates and curve must share a nonzero prime");
+        if self.is_identity() {
+            return *rhs;
+        }
+        if rhs.is_identity() {
+            return *self;
+        }
+        if let (Some(x1, y1), Some(x2, y2)) = (&self.coords, &rhs.coords) {
+            if x1 != x2 {
+                let s = (*y2 - *y1) / (*x2 - *x1);
+                let x3 = s * s - *x1 - *x2;
+                let y3 = s * (*x1 - x3) - *y1;
+                return Point { coords: Some(x3, y3), curve: self.curve };
+            } else if y1 == y2 {
+                return self.double();
+            }
+        }
+        // x1 == x2 and y1 != y2: the points are inverses
+        Point { coords: Identity, curve: self.curve }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.coords.is_identity()
+    }
+
+    // is_identity as a Choice, for constant-time callers. This reads the
+    // Coords discriminant with no branch of its own, but the enum layout
+    // means the compiler is free to branch on it elsewhere; code that must
+    // not leak whether a point is the identity should stay in Jacobian
+    // form, where JacobianPoint::ct_is_identity compares Z to zero in
+    // constant time.
+    pub fn ct_is_identity(&self) -> Choice {
+        Choice::from(self.coords.is_identity() as u8)
+    }
+
+    // The tangent step. A point with y == 0 is its own inverse, so
+    // doubling it gives the identity.
+    pub fn double(&self) -> Point<LIMBS> {
+        if let Some(x1, y1) = &self.coords {
+            if y1.get_num() == Uint::ZERO {
+                return Point { coords: Identity, curve: self.curve };
+            }
+            let xx = *x1 * *x1;
+            let s = (xx + xx + xx + self.curve.a) / (*y1 + *y1);
+            let x3 = s * s - *x1 - *x1;
+            let y3 = s * (*x1 - x3) - *y1;
+            Point { coords: Some(x3, y3), curve: self.curve }
         } else {
-            if let Some(_x2, _y2) = rhs.coords {
-                return rhs;    
-            }    
+            *self
+        }
+    }
+
+    // The chord step, without add_ref's doubling check on y. Equal x or an
+    // identity operand falls back to add_ref; scalar mul rarely hits those.
+    fn add_distinct(&self, rhs: &Self) -> Point<LIMBS> {
+        if let (Some(x1, y1), Some(x2, y2)) = (&self.coords, &rhs.coords) {
+            if x1 != x2 {
+                let s = (*y2 - *y1) / (*x2 - *x1);
+                let x3 = s * s - *x1 - *x2;
+                let y3 = s * (*x1 - x3) - *y1;
+                return Point { coords: Some(x3, y3), curve: self.curve };
+            }
+        }
+        self.add_ref(rhs)
+    }
+}
+
+impl<const LIMBS: usize> Point<LIMBS> {
+    // P, 2P, 3P, ... stopping before the identity
+    pub fn multiples(&self) -> MultiplesIter<LIMBS> {
+        MultiplesIter {
+            base: *self,
+            next: *self
         }
-        Point::new(Identity, self.curve)        
+    }
+
+    // P, 2P, ... and finally the identity, i.e. the whole subgroup <P>,
+    // stopping early after max points. Only sensible for small orders.
+    pub fn subgroup(&self, max: usize) -> Vec<Point<LIMBS>> {
+        let mut points: Vec<Point<LIMBS>> = self.multiples().take(max).collect();
+        // multiples() ran out, so the next multiple is the identity
+        if points.len() < max {
+            points.push(Point { coords: Identity, curve: self.curve });
+        }
+        points
+    }
+}
+
+pub struct MultiplesIter<const LIMBS: usize> {
+    base: Point<LIMBS>,
+    next: Point<LIMBS>,
+}
+
+impl<const LIMBS: usize> Iterator for MultiplesIter<LIMBS> {
+    type Item = Point<LIMBS>;
+    fn next(&mut self) -> Option<Point<LIMBS>> {
+        if self.next.is_identity() {
+            return None;
+        }
+        let current = self.next;
+        self.next = current.add_ref(&self.base);
+        Option::Some(current)
+    }
+}
+
+// panics if the two points are on different curves; checked_add returns
+// an error instead
+impl<const LIMBS: usize> Add for Point<LIMBS> {
+    type Output = Point<LIMBS>;
+    fn add(self, rhs: Self) -> Point<LIMBS> {
+        self.add_ref(&rhs)
     }    
 }
 
+impl<const LIMBS: usize> Add<&Point<LIMBS>> for &Point<LIMBS> {
+    type Output = Point<LIMBS>;
+    fn add(self, rhs: &Point<LIMBS>) -> Point<LIMBS> {
+        self.add_ref(rhs)
+    }
+}
+
+impl<const LIMBS: usize> Add<&Point<LIMBS>> for Point<LIMBS> {
+    type Output = Point<LIMBS>;
+    fn add(self, rhs: &Point<LIMBS>) -> Point<LIMBS> {
+        self.add_ref(rhs)
+    }
+}
+
+impl<const LIMBS: usize> Add<Point<LIMBS>> for &Point<LIMBS> {
+    type Output = Point<LIMBS>;
+    fn add(self, rhs: Point<LIMBS>) -> Point<LIMBS> {
+        self.add_ref(&rhs)
+    }
+}
+
 impl<const LIMBS: usize> Mul<Point<LIMBS>> for Uint<LIMBS> {
     type Output = Point<LIMBS>;
     fn mul(self, rhs: Point<LIMBS>) -> Self::Output {
@@ -87,14 +350,24 @@ impl<const LIMBS: usize> Mul<Point<LIMBS>> for Uint<LIMBS> {
         let one = Uint::ONE;
         assert!(coef >= zero);
 
+        // k * O = O, so don't walk the bits of k at all
+        if rhs.is_identity() {
+            return rhs;
+        }
+
+        // The curve is validated once, here: result starts on rhs's curve
+        // and current is always a multiple of rhs, so every add_distinct
+        // below is between points sharing that curve and its prime
+        assert!(shares_prime(&rhs.coords, &rhs.curve), "coordinates and curve must share a nonzero prime");
         let mut current = rhs;
-        let mut result = Point::new(Identity, rhs.curve);
+        let mut result = Point { coords: Identity, curve: rhs.curve };
 
+        // current is always a doubling, result + current almost always a chord
         while coef > zero {
             if coef & one > zero {
-                result = result + current;    
+                result = result.add_distinct(&current);    
             }    
-            current = current + current;
+            current = current.double();
             coef = coef >> (1_usize);
         }                
 
@@ -105,11 +378,104 @@ impl<const LIMBS: usize> Mul<Point<LIMBS>> for Uint<LIMBS> {
     }    
 }
 
+// Widening toy-curve values from the generic backend, e.g. Point<u16>
+// to Point<4>, so the two group laws can be checked against each other.
+// Coordinates are copied as they are, without re-checking the curve.
+fn widen<T: Copy + Into<u64>, const LIMBS: usize>(e: &finite_field::FieldElement<T>) -> FieldElementBig<LIMBS> {
+    FieldElementBig::new(Uint::from_u64(e.get_num().into()), Uint::from_u64(e.get_prime().into()))
+}
+
+impl<T: Copy + Into<u64>, const LIMBS: usize> From<crate::elliptic_curves::EllipticCurve<T>> for EllipticCurve<LIMBS> {
+    fn from(curve: crate::elliptic_curves::EllipticCurve<T>) -> EllipticCurve<LIMBS> {
+        EllipticCurve { a: widen(&curve.a), b: widen(&curve.b) }
+    }
+}
+
+impl<T: Copy + Into<u64>, const LIMBS: usize> From<crate::elliptic_curves::Coords<T>> for Coords<LIMBS> {
+    fn from(coords: crate::elliptic_curves::Coords<T>) -> Coords<LIMBS> {
+        match coords {
+            crate::elliptic_curves::Coords::Some(x, y) => Some(widen(&x), widen(&y)),
+            crate::elliptic_curves::Coords::Identity => Identity,
+        }
+    }
+}
+
+impl<T: Copy + Into<u64>, const LIMBS: usize> From<crate::elliptic_curves::Point<T>> for Point<LIMBS> {
+    fn from(point: crate::elliptic_curves::Point<T>) -> Point<LIMBS> {
+        Point { coords: point.coords().into(), curve: point.curve().into() }
+    }
+}
+
+// Bit i of value, counting from the least significant bit; bits past the
+// top of the integer read as 0. The primitive under every mul loop here.
+pub fn scalar_bit<const LIMBS: usize>(value: &Uint<LIMBS>, i: usize) -> bool {
+    i < Uint::<LIMBS>::BITS && value.bit_vartime(i)
+}
+
+// The bits of scalar from the most significant set bit down to bit 0, for
+// the MSB-first (Horner style) mul loops. Zero yields no bits.
+pub fn bits_msb_first<const LIMBS: usize>(scalar: Uint<LIMBS>) -> impl Iterator<Item = bool> {
+    (0..scalar.bits_vartime()).rev().map(move |i| scalar_bit(&scalar, i))
+}
+
+// The same double-and-add as Uint * Point, kept deliberately plain for
+// teaching and debugging: after each bit of the scalar it pushes the
+// running result onto log, so log ends up with one entry per bit.
+pub fn scalar_mul_verbose<const LIMBS: usize>(scalar: Uint<LIMBS>, point: Point<LIMBS>, log: &mut Vec<Point<LIMBS>>) -> Point<LIMBS> {
+    let mut coef = scalar;
+    let zero = Uint::ZERO;
+    let one = Uint::ONE;
+
+    let mut current = point;
+    let mut result = Point::new(Identity, point.curve);
+    while coef > zero {
+        if coef & one > zero {
+            result = result + current;
+        }
+        log.push(result);
+        current = current + current;
+        coef >>= 1;
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crypto_bigint::U256;
 
+    #[test]
+    fn scalar_bit_works() {
+        let value = U256::from(0b1011_0010u8);
+        let bits: Vec<bool> = (0..8).map(|i| scalar_bit(&value, i)).collect();
+        assert_eq!(bits, [false, true, false, false, true, true, false, true]);
+        assert!(!scalar_bit(&value, 8));
+
+        // the top bit, and past the end
+        let n = U256::from_be_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        assert!(scalar_bit(&n, 255));
+        assert!(scalar_bit(&n, 0));
+        assert!(!scalar_bit(&n, 1));
+        assert!(!scalar_bit(&n, 256));
+        assert!(!scalar_bit(&U256::MAX, 1000));
+    }
+
+    #[test]
+    fn bits_msb_first_works() {
+        let scalar = U256::from(0b1011_0010u8);
+        let bits: Vec<bool> = bits_msb_first(scalar).collect();
+        assert_eq!(bits, [true, false, true, true, false, false, true, false]);
+
+        // Horner's rule on the bits rebuilds the scalar
+        for scalar in [U256::ONE, U256::from(123456789u32), U256::MAX] {
+            let rebuilt = bits_msb_first(scalar)
+                .fold(U256::ZERO, |acc, bit| acc.shl_vartime(1) | U256::from(bit as u8));
+            assert_eq!(rebuilt, scalar);
+        }
+        assert_eq!(bits_msb_first(U256::ZERO).count(), 0);
+    }
+
     #[test]
     fn curve_works() {
 
@@ -147,6 +513,29 @@ mod tests {
     }
 
 
+    #[test]
+    fn adding_identity_returns_the_input() {
+        let p = U256::from(223u8);
+        let a = FieldElementBig::new(U256::from(0u8), p);
+        let b = FieldElementBig::new(U256::from(7u8), p);
+        let curve = EllipticCurve {a, b};
+        let zero = Point::new(Identity, curve);
+
+        // (1, 1) is off the curve, so Point::new would panic on it; adding
+        // the identity hands it back untouched instead of rebuilding it
+        let one = FieldElementBig::new(U256::ONE, p);
+        let off_curve = Point { coords: Some(one, one), curve };
+        assert_eq!(off_curve + zero, off_curve);
+        assert_eq!(zero + off_curve, off_curve);
+        assert_eq!(zero + zero, zero);
+
+        let x = FieldElementBig::new(U256::from(47u8), p);
+        let y = FieldElementBig::new(U256::from(71u8), p);
+        let point = Point::new(Some(x, y), curve);
+        assert_eq!(point + zero, point);
+        assert_eq!(zero.add_ref(&point), point);
+    }
+
     #[test]
     fn identity_works() {
         ////////////////// Curve
@@ -210,5 +599,388 @@ mod tests {
         assert_eq!(point3, zero);                
     }    
 
+    #[test]
+    // the borrowed impls are what is under test here
+    #[allow(clippy::op_ref)]
+    fn add_ref_matches_known_results() {
+        ////////////////// Curve
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+        let point = |x: u8, y: u8| {
+            let x = FieldElementBig::new(U256::from(x), U256::from(223u8));
+            let y = FieldElementBig::new(U256::from(y), U256::from(223u8));
+            Point::new(Some(x, y), curve)
+        };
+
+        // (192, 105) + (17, 56) = (170, 142)
+        assert_eq!(point(192, 105).add_ref(&point(17, 56)), point(170, 142));
+        // (47, 71) + (117, 141) = (60, 139)
+        assert_eq!(point(47, 71).add_ref(&point(117, 141)), point(60, 139));
+        // 2 * (47, 71) = (36, 111), 2 * (192, 105) = (49, 71)
+        assert_eq!(point(47, 71).double(), point(36, 111));
+        assert_eq!(point(192, 105).double(), point(49, 71));
+
+        // the owned Add gives the same answers
+        assert_eq!(point(192, 105) + point(17, 56), point(170, 142));
+        assert_eq!(point(47, 71) + point(47, 71), point(36, 111));
+
+        // and so do the borrowed ones
+        let (p, q) = (point(192, 105), point(17, 56));
+        assert_eq!(&p + &q, point(170, 142));
+        assert_eq!(p + &q, point(170, 142));
+        assert_eq!(&p + q, point(170, 142));
+        let doubled: Vec<Point<4>> = [p, q].iter().map(|point| point + point).collect();
+        assert_eq!(doubled, vec![point(49, 71), q.double()]);
+    }
+
+    #[test]
+    fn debug_prints_hex() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = format!("{:?}", secp256k1.get_generator_point());
+        assert!(g.starts_with("Point { coords: Some(0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798, 0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8)"), "{}", g);
+        assert!(g.contains("curve: EllipticCurve { a: 0x0, b: 0x7, p: 0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f }"), "{}", g);
+
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert!(format!("{:?}", zero).starts_with("Point { coords: Identity, "));
+        assert_eq!(format!("{:?}", Coords::<4>::Identity), "Identity");
+    }
+
+    #[test]
+    fn ct_is_identity_works() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        for point in [g, g.double(), zero, g + zero] {
+            assert_eq!(bool::from(point.ct_is_identity()), point.is_identity());
+        }
+        assert!(bool::from(zero.ct_is_identity()));
+        assert!(!bool::from(g.ct_is_identity()));
+    }
+
+    #[test]
+    fn mul_small_works() {
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+        let x = FieldElementBig::new(U256::from(47u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(71u8), U256::from(223u8));
+        let point = Point::new(Some(x, y), curve);
+
+        assert_eq!(point.mul_small(21), U256::from(21u8) * point);
+        assert!(point.mul_small(21).is_identity());
+        assert_eq!(point.mul_small(0), Point::new(Identity, curve));
+        assert_eq!(point.mul_small(1), point);
+        assert_eq!(point.mul_small(u64::MAX), U256::from(u64::MAX) * point);
+
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        assert_eq!(g.mul_small(4), secp256k1.get_public_key(U256::from(4u8)));
+    }
+
+    #[test]
+    fn add_with_flag_works() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let g2 = g.double();
+        let neg_g = secp256k1.get_public_key(secp256k1.get_group_order().wrapping_sub(&U256::ONE));
+        let zero = Point::new(Identity, secp256k1.get_curve());
+
+        assert_eq!(g.add_with_flag(g2), (g + g2, AddOutcome::Normal));
+        assert_eq!(g.add_with_flag(g), (g2, AddOutcome::Doubled));
+        assert_eq!(g.add_with_flag(neg_g), (zero, AddOutcome::Inverses));
+        assert_eq!(g.add_with_flag(zero), (g, AddOutcome::IdentityOperand));
+        assert_eq!(zero.add_with_flag(g), (g, AddOutcome::IdentityOperand));
+        assert_eq!(zero.add_with_flag(zero), (zero, AddOutcome::IdentityOperand));
+    }
+
+    #[test]
+    fn double_edge_cases_work() {
+        // y^2 = x^3 + 7 over F_223, where 6^3 + 7 = 223, so (6, 0) is on the
+        // curve and its tangent is vertical
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+        let point = |x: u8, y: u8| {
+            let x = FieldElementBig::new(U256::from(x), U256::from(223u8));
+            let y = FieldElementBig::new(U256::from(y), U256::from(223u8));
+            Point::new(Some(x, y), curve)
+        };
+        let zero = Point::new(Identity, curve);
+
+        let two_torsion = point(6, 0);
+        assert!(curve.contains(&two_torsion));
+        assert_eq!(two_torsion.double(), zero);
+        assert_eq!(two_torsion + two_torsion, zero);
+        assert_eq!(zero.double(), zero);
+        for p in [point(47, 71), point(192, 105), point(17, 56)] {
+            assert_eq!(p.double(), p + p);
+        }
+
+        // secp256k1 has no point with y = 0 (x^3 = -7 has no root mod p)
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert_eq!(zero.double(), zero);
+        assert_eq!(g.double(), g + g);
+        assert_eq!(g.double(), secp256k1.get_public_key(U256::from(2u8)));
+        let q = secp256k1.get_public_key(secp256k1.get_secret_key());
+        assert_eq!(q.double(), q + q);
+    }
+
+    #[test]
+    fn zero_modulus_is_rejected() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let curve = secp256k1.get_curve();
+        let g = secp256k1.get_generator_point();
+        let (gx, gy) = match g.coords {
+            Some(x, y) => (x, y),
+            Identity => unreachable!(),
+        };
+
+        // a coordinate with modulus 0, as a default-constructed one would be
+        let broken_x = FieldElementBig::new(gx.get_num(), U256::ZERO);
+        assert_eq!(Point::try_new(Some(broken_x, gy), curve), Err(EcError::InvalidParameters));
+
+        // a curve over "F_0"
+        let zero = FieldElementBig::new(U256::ZERO, U256::ZERO);
+        let broken_curve = EllipticCurve { a: zero, b: zero };
+        assert_eq!(Point::try_new(Identity, broken_curve), Err(EcError::InvalidParameters));
+
+        // a coordinate from another field
+        let other_x = FieldElementBig::new(U256::ONE, U256::from(223u8));
+        assert_eq!(Point::try_new(Some(other_x, gy), curve), Err(EcError::InvalidParameters));
+
+        // struct literals get past the constructors, but not checked_add
+        let broken = Point { coords: Some(broken_x, gy), curve };
+        assert_eq!(g.checked_add(broken), Err(EcError::InvalidParameters));
+        assert_eq!(broken.checked_add(g), Err(EcError::InvalidParameters));
+        assert_eq!(g.checked_add(g), Ok(g.double()));
+    }
+
+    #[test]
+    #[should_panic(expected = "share a nonzero prime")]
+    fn adding_a_zero_modulus_point_panics() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let zero = FieldElementBig::new(U256::ZERO, U256::ZERO);
+        let broken = Point { coords: Some(zero, zero), curve: g.curve };
+        let _ = g + broken;
+    }
+
+    #[test]
+    fn fallible_constructors_work() {
+        ////////////////// Curve
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+
+        // (192, 105) is on the curve, (192, 106) is not
+        let x = FieldElementBig::new(U256::from(192u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(105u8), U256::from(223u8));
+        let point = Point::try_new(Some(x, y), curve).unwrap();
+        let y = FieldElementBig::new(U256::from(106u8), U256::from(223u8));
+        assert_eq!(Point::try_new(Some(x, y), curve), Err(EcError::NotOnCurve));
+
+        // adding points of different curves errors instead of panicking
+        let b = FieldElementBig::new(U256::from(5u8), U256::from(223u8));
+        let other = Point::new(Identity, EllipticCurve {a, b});
+        assert_eq!(point.checked_add(other), Err(EcError::CurveMismatch));
+        assert_eq!(point.checked_add(point), Ok(point + point));
+    }
+
+    #[test]
+    fn contains_works() {
+        ////////////////// Curves
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+        let b = FieldElementBig::new(U256::from(5u8), U256::from(223u8));
+        let other = EllipticCurve {a, b};
+
+        let x = FieldElementBig::new(U256::from(47u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(71u8), U256::from(223u8));
+        let point = Point::new(Some(x, y), curve);
+        let zero = Point::new(Identity, curve);
+
+        assert!(curve.is_on_curve(&point.coords));
+        assert!(!other.is_on_curve(&point.coords));
+        assert!(curve.contains(&point));
+        assert!(curve.contains(&zero));
+
+        // a point built on a different curve is not contained
+        assert!(!other.contains(&point));
+        assert!(!other.contains(&zero));
+    }
+
+    #[test]
+    fn is_identity_works() {
+        ////////////////// Curve
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+
+        let x = FieldElementBig::new(U256::from(47u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(71u8), U256::from(223u8));
+        let point = Point::new(Some(x, y), curve);
+        let zero = Point::new(Identity, curve);
+
+        assert!(!point.is_identity());
+        assert!(!point.coords.is_identity());
+        assert!(zero.is_identity());
+        assert!(zero.coords.is_identity());
+        assert!((U256::from(21u8) * point).is_identity());
+    }
+
+    #[test]
+    fn identity_equality_is_per_curve() {
+        ////////////////// Curves
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+        let b = FieldElementBig::new(U256::from(5u8), U256::from(223u8));
+        let other = EllipticCurve {a, b};
+
+        let zero = Point::new(Identity, curve);
+        let other_zero = Point::new(Identity, other);
+
+        // same curve built twice: equal
+        assert_eq!(zero, Point::new(Identity, EllipticCurve::new(curve.a, curve.b)));
+        // different curves: not equal, though both are the identity
+        assert_ne!(zero, other_zero);
+        assert!(zero.is_identity() && other_zero.is_identity());
+    }
+
+    #[test]
+    fn multiples_works() {
+        ////////////////// Curve
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+
+        let x = FieldElementBig::new(U256::from(47u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(71u8), U256::from(223u8));
+        let point = Point::new(Some(x, y), curve);
+
+        // the first few multiples agree with scalar multiplication
+        for (k, multiple) in point.multiples().take(5).enumerate() {
+            assert_eq!(multiple, U256::from(k as u8 + 1) * point);
+        }
+
+        // (47, 71) has order 21, so the stream stops after 20P
+        assert_eq!(point.multiples().count(), 20);
+    }
+
+    #[test]
+    fn normalized_works() {
+        let p = U256::from(223u8);
+        let curve = EllipticCurve::new(FieldElementBig::new(U256::ZERO, p), FieldElementBig::new(U256::from(7u8), p));
+        // a = p and b = p + 7 are the same curve, written unreduced
+        let raw = EllipticCurve::new(FieldElementBig::new(p, p), FieldElementBig::new(U256::from(230u8), p));
+
+        assert_eq!(raw.normalized(), curve);
+        assert_eq!(curve.normalized(), curve);
+        assert_eq!(raw.normalized().normalized(), raw.normalized());
+
+        let inside = Some(FieldElementBig::new(U256::from(47u8), p), FieldElementBig::new(U256::from(71u8), p));
+        let outside = Some(FieldElementBig::new(U256::from(47u8), p), FieldElementBig::new(U256::from(72u8), p));
+        for coords in [inside, outside] {
+            assert_eq!(raw.is_on_curve(&coords), curve.is_on_curve(&coords));
+            assert_eq!(raw.normalized().is_on_curve(&coords), curve.is_on_curve(&coords));
+        }
+        assert!(curve.contains(&Point::new(inside, raw.normalized())));
+    }
+
+    #[test]
+    fn subgroup_works() {
+        let p = U256::from(223u8);
+        let a = FieldElementBig::new(U256::from(0u8), p);
+        let b = FieldElementBig::new(U256::from(7u8), p);
+        let curve = EllipticCurve {a, b};
+        let zero = Point::new(Identity, curve);
+
+        let x = FieldElementBig::new(U256::from(47u8), p);
+        let y = FieldElementBig::new(U256::from(71u8), p);
+        let point = Point::new(Some(x, y), curve);
+
+        // (47, 71) has order 21: 20 affine multiples, then the identity
+        let subgroup = point.subgroup(100);
+        assert_eq!(subgroup.len(), 21);
+        assert_eq!(subgroup[0], point);
+        assert_eq!(subgroup[6], U256::from(7u8) * point);
+        assert_eq!(subgroup[20], zero);
+        assert!(subgroup.iter().all(|q| curve.contains(q)));
+
+        // bounded, and exactly the order lets the identity in
+        assert_eq!(point.subgroup(5), point.multiples().take(5).collect::<Vec<_>>());
+        assert_eq!(point.subgroup(21), subgroup);
+        assert_eq!(point.subgroup(20).len(), 20);
+        assert!(point.subgroup(0).is_empty());
+        assert_eq!(zero.subgroup(10), vec![zero]);
+    }
+
+    #[test]
+    fn scalar_mul_verbose_works() {
+        ////////////////// Curve
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+
+        let x = FieldElementBig::new(U256::from(47u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(71u8), U256::from(223u8));
+        let point = Point::new(Some(x, y), curve);
+
+        // 13 = 0b1101: after each bit the result is P, P, 5P, 13P
+        let mut log = Vec::new();
+        let scalar = U256::from(13u8);
+        let result = scalar_mul_verbose(scalar, point, &mut log);
+        assert_eq!(result, scalar * point);
+        assert_eq!(log.len(), scalar.bits_vartime());
+        assert_eq!(log, vec![point, point, U256::from(5u8) * point, result]);
+    }
+
+    #[test]
+    fn scalar_mul_with_double_matches_plain_addition() {
+        ////////////////// Curve
+        let a = FieldElementBig::new(U256::from(0u8), U256::from(223u8));
+        let b = FieldElementBig::new(U256::from(7u8), U256::from(223u8));
+        let curve = EllipticCurve {a, b};
+
+        let x = FieldElementBig::new(U256::from(47u8), U256::from(223u8));
+        let y = FieldElementBig::new(U256::from(71u8), U256::from(223u8));
+        let point = Point::new(Some(x, y), curve);
+
+        // scalars past the order 21 make result and current collide
+        for k in 0u8..64 {
+            let mut log = Vec::new();
+            let plain = scalar_mul_verbose(U256::from(k), point, &mut log);
+            assert_eq!(U256::from(k) * point, plain);
+        }
+    }
+
+    #[test]
+    fn scalar_mul_identity_short_circuits() {
+        // the identity of a curve over "F_0": the curve check and the loop
+        // would both reject it, so getting it back unchanged shows the early
+        // return ran before them
+        let zero = FieldElementBig::new(U256::ZERO, U256::ZERO);
+        let broken_identity = Point { coords: Identity, curve: EllipticCurve { a: zero, b: zero } };
+        assert_eq!(U256::MAX * broken_identity, broken_identity);
+
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let identity = Point::new(Identity, secp256k1.get_curve());
+        assert_eq!(U256::MAX * identity, identity);
+    }
+
+    #[test]
+    #[should_panic(expected = "share a nonzero prime")]
+    fn scalar_mul_checks_the_curve_up_front() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let zero = FieldElementBig::new(U256::ZERO, U256::ZERO);
+        let broken = Point { coords: Some(zero, zero), curve: g.curve };
+        let _ = U256::from(3u8) * broken;
+    }
+
 
 }
\ No newline at end of file
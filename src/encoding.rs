@@ -0,0 +1,636 @@
+use finite_field::FieldElementBig;
+use crate::elliptic_curves_bigint::{Coords, EllipticCurve, Point};
+use crate::error::{EcError, ParseError};
+use crate::field_ext::{PowU32, Sqrt};
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::{Encoding, U256};
+use num_bigint::BigUint;
+use Coords::{Some, Identity};
+
+pub(crate) fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, ParseError> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(ParseError::InvalidLength);
+    }
+    // from_str_radix alone would take a leading sign, e.g. "+f"
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ParseError::InvalidHex);
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or(ParseError::InvalidHex)
+        })
+        .collect()
+}
+
+// sqrt(x^3 + ax + b) on any curve, with the requested parity, or None if
+// x isn't the x-coordinate of a point
+pub(crate) fn y_from_x_on(curve: EllipticCurve<4>, x: U256, want_even: bool) -> Option<U256> {
+    let p = curve.a.get_prime();
+    if x >= p {
+        return None;
+    }
+    let x = FieldElementBig::new(x, p);
+    let beta = (x.pow_u32(3) + curve.a * x + curve.b).sqrt()?;
+    let is_even = !beta.get_num().bit_vartime(0);
+    let y = if is_even == want_even {
+        beta
+    } else {
+        FieldElementBig::new(U256::ZERO, p) - beta
+    };
+    Option::Some(y.get_num())
+}
+
+// a compressed SEC1 key 0x02 / 0x03 || x on any curve
+pub(crate) fn point_from_compressed(curve: EllipticCurve<4>, sec: &[u8; 33]) -> Result<Point<4>, ParseError> {
+    let want_even = match sec[0] {
+        0x02 => true,
+        0x03 => false,
+        _ => return Err(ParseError::InvalidPrefix),
+    };
+    let p = curve.a.get_prime();
+    let x = U256::from_be_slice(&sec[1..]);
+    if x >= p {
+        return Err(ParseError::OutOfRange);
+    }
+    let y = y_from_x_on(curve, x, want_even).ok_or(ParseError::NotOnCurve)?;
+    let coords = Some(FieldElementBig::new(x, p), FieldElementBig::new(y, p));
+    Ok(Point::new(coords, curve))
+}
+
+// The public key encodings told apart by length and prefix alone
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PubkeyFormat {
+    // 0x02 / 0x03 || x
+    Compressed,
+    // 0x04 || x || y
+    Uncompressed,
+    // BIP 340's bare 32-byte x
+    XOnly,
+    // SEC1's single 0x00 byte
+    Identity,
+}
+
+// Which encoding bytes claim to be, for dispatching to the right parser.
+// Nothing is checked beyond length and prefix, so the point may still be
+// off the curve.
+pub fn detect_pubkey_format(bytes: &[u8]) -> Option<PubkeyFormat> {
+    match (bytes.len(), bytes.first()) {
+        (33, Option::Some(0x02 | 0x03)) => Option::Some(PubkeyFormat::Compressed),
+        (65, Option::Some(0x04)) => Option::Some(PubkeyFormat::Uncompressed),
+        (32, _) => Option::Some(PubkeyFormat::XOnly),
+        (1, Option::Some(0x00)) => Option::Some(PubkeyFormat::Identity),
+        _ => None,
+    }
+}
+
+// 32-byte scalars (secret keys) in either byte order
+pub fn scalar_to_bytes_be(scalar: U256) -> [u8; 32] {
+    scalar.to_be_bytes()
+}
+
+pub fn scalar_to_bytes_le(scalar: U256) -> [u8; 32] {
+    scalar.to_le_bytes()
+}
+
+pub fn scalar_from_bytes_be(bytes: &[u8; 32]) -> U256 {
+    U256::from_be_bytes(*bytes)
+}
+
+pub fn scalar_from_bytes_le(bytes: &[u8; 32]) -> U256 {
+    U256::from_le_bytes(*bytes)
+}
+
+// raw x || y, 32 bytes each big-endian, without any prefix byte
+impl TryFrom<Point<4>> for [u8; 64] {
+    type Error = EcError;
+    fn try_from(point: Point<4>) -> Result<[u8; 64], EcError> {
+        match point.coords {
+            Some(x, y) => {
+                let mut raw = [0u8; 64];
+                raw[..32].copy_from_slice(&x.get_num().to_be_bytes());
+                raw[32..].copy_from_slice(&y.get_num().to_be_bytes());
+                Ok(raw)
+            }
+            Identity => Err(EcError::Identity),
+        }
+    }
+}
+
+// parses a raw x || y as a secp256k1 point, checking both coordinates are
+// below p and the point is on the curve
+impl TryFrom<[u8; 64]> for Point<4> {
+    type Error = EcError;
+    fn try_from(raw: [u8; 64]) -> Result<Point<4>, EcError> {
+        SECP256K1::new().point_from_bytes_be(&raw)
+    }
+}
+
+// x || y with each coordinate in the given byte order; x always comes first
+impl Point<4> {
+    pub fn to_bytes_be(&self) -> Result<[u8; 64], EcError> {
+        <[u8; 64]>::try_from(*self)
+    }
+
+    pub fn to_bytes_le(&self) -> Result<[u8; 64], EcError> {
+        let mut raw = self.to_bytes_be()?;
+        raw[..32].reverse();
+        raw[32..].reverse();
+        Ok(raw)
+    }
+}
+
+// the SEC1 encodings that SECP256K1::from_sec reads back
+impl Point<4> {
+    pub fn to_sec_compressed(&self) -> Result<[u8; 33], EcError> {
+        let raw = self.to_bytes_be()?;
+        let mut sec = [0u8; 33];
+        sec[0] = if raw[63] & 1 == 0 { 0x02 } else { 0x03 };
+        sec[1..].copy_from_slice(&raw[..32]);
+        Ok(sec)
+    }
+
+    pub fn to_sec_uncompressed(&self) -> Result<[u8; 65], EcError> {
+        let raw = self.to_bytes_be()?;
+        let mut sec = [0u8; 65];
+        sec[0] = 0x04;
+        sec[1..].copy_from_slice(&raw);
+        Ok(sec)
+    }
+
+    // compares against a compressed or uncompressed SEC1 encoding; the
+    // identity only matches SEC1's single 0x00 byte
+    pub fn eq_sec(&self, bytes: &[u8]) -> bool {
+        if self.is_identity() {
+            return bytes == [0x00];
+        }
+        match bytes.len() {
+            33 => self.to_sec_compressed().is_ok_and(|sec| sec[..] == *bytes),
+            65 => self.to_sec_uncompressed().is_ok_and(|sec| sec[..] == *bytes),
+            _ => false,
+        }
+    }
+
+    // A multi-line dump for debugging and teaching: the curve equation,
+    // the coordinates in hex and decimal, whether the point is on the
+    // curve, and its compressed SEC1 form.
+    pub fn report(&self) -> String {
+        let decimal = |e: &FieldElementBig<4>| BigUint::from_bytes_be(&e.get_num().to_be_bytes()).to_string();
+        let mut report = format!(
+            "curve:      y^2 = x^3 + {} x + {} (mod {})\n",
+            decimal(&self.curve.a),
+            decimal(&self.curve.b),
+            BigUint::from_bytes_be(&self.curve.a.get_prime().to_be_bytes()),
+        );
+        match self.coords {
+            Some(x, y) => {
+                report += &format!("x:          0x{:x} ({})\n", x.get_num(), decimal(&x));
+                report += &format!("y:          0x{:x} ({})\n", y.get_num(), decimal(&y));
+            }
+            Identity => report += "point:      identity\n",
+        }
+        report += &format!("on curve:   {}\n", self.curve.is_on_curve(&self.coords));
+        match self.to_sec_compressed() {
+            Ok(sec) => report += &format!("compressed: {}", bytes_to_hex(&sec)),
+            Err(_) => report += "compressed: none",
+        }
+        report
+    }
+}
+
+impl SECP256K1 {
+    // raw x || y in either byte order; coordinates of p or more are
+    // rejected, not reduced
+    pub fn point_from_bytes_be(&self, raw: &[u8; 64]) -> Result<Point<4>, EcError> {
+        self.point_from_coords_strict(U256::from_be_slice(&raw[..32]), U256::from_be_slice(&raw[32..]))
+    }
+
+    pub fn point_from_bytes_le(&self, raw: &[u8; 64]) -> Result<Point<4>, EcError> {
+        self.point_from_coords_strict(U256::from_le_slice(&raw[..32]), U256::from_le_slice(&raw[32..]))
+    }
+
+    // (x, y) as a curve point, with x and y required to be below p rather
+    // than reduced mod p, so a mis-encoded coordinate can't slip through as
+    // a different value
+    pub fn point_from_coords_strict(&self, x: U256, y: U256) -> Result<Point<4>, EcError> {
+        let p = self.get_order();
+        if x >= p || y >= p {
+            return Err(EcError::OutOfRange);
+        }
+        Point::try_new(Some(FieldElementBig::new(x, p), FieldElementBig::new(y, p)), self.get_curve())
+    }
+
+    // field coordinates from up to 64 hex digits each, left padded with
+    // zeros; values of p or more aren't field elements
+    pub fn coords_from_hex(&self, x_hex: &str, y_hex: &str) -> Result<Coords<4>, ParseError> {
+        let p = self.get_order();
+        let parse = |hex: &str| -> Result<FieldElementBig<4>, ParseError> {
+            if hex.is_empty() || hex.len() > 64 {
+                return Err(ParseError::InvalidLength);
+            }
+            let bytes = hex_to_bytes(&format!("{:0>64}", hex))?;
+            let num = U256::from_be_slice(&bytes);
+            if num >= p {
+                return Err(ParseError::OutOfRange);
+            }
+            Ok(FieldElementBig::new(num, p))
+        };
+        Ok(Some(parse(x_hex)?, parse(y_hex)?))
+    }
+
+    // -P from P's compressed form: same x, other y parity, so only the
+    // prefix changes. The input isn't validated.
+    #[must_use]
+    pub fn negate_compressed(&self, sec: &[u8; 33]) -> [u8; 33] {
+        let mut negated = *sec;
+        negated[0] ^= 0x01;
+        negated
+    }
+
+    // sqrt(x^3 + 7) with the requested parity, or None if x isn't the
+    // x-coordinate of any curve point
+    pub fn y_from_x(&self, x: U256, want_even: bool) -> Option<U256> {
+        y_from_x_on(self.get_curve(), x, want_even)
+    }
+
+    // both roots (y, p - y) for x, even one first
+    pub fn both_ys(&self, x: U256) -> Option<(U256, U256)> {
+        let even = self.y_from_x(x, true)?;
+        Option::Some((even, self.get_order().wrapping_sub(&even)))
+    }
+
+    pub fn point_from_x(&self, x: U256, even_y: bool) -> Option<Point<4>> {
+        let y = self.y_from_x(x, even_y)?;
+        let p = self.get_order();
+        let coords = Some(FieldElementBig::new(x, p), FieldElementBig::new(y, p));
+        Option::Some(Point::new(coords, self.get_curve()))
+    }
+
+    // SEC1 public key: 0x02/0x03 || x (compressed) or 0x04 || x || y (uncompressed)
+    pub fn from_sec(&self, bytes: &[u8]) -> Result<Point<4>, ParseError> {
+        let p = self.get_order();
+        let curve = self.get_curve();
+        let prefix = *bytes.first().ok_or(ParseError::InvalidLength)?;
+        match prefix {
+            0x02 | 0x03 => {
+                let sec: &[u8; 33] = bytes.try_into().map_err(|_| ParseError::InvalidLength)?;
+                point_from_compressed(curve, sec)
+            }
+            0x04 => {
+                if bytes.len() != 65 {
+                    return Err(ParseError::InvalidLength);
+                }
+                let x = U256::from_be_slice(&bytes[1..33]);
+                let y = U256::from_be_slice(&bytes[33..]);
+                if x >= p || y >= p {
+                    return Err(ParseError::OutOfRange);
+                }
+                let coords = Some(FieldElementBig::new(x, p), FieldElementBig::new(y, p));
+                Point::try_new(coords, curve).map_err(|_| ParseError::NotOnCurve)
+            }
+            _ => Err(ParseError::InvalidPrefix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_pubkey_format_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let compressed = g.to_sec_compressed().unwrap();
+        let uncompressed = g.to_sec_uncompressed().unwrap();
+
+        assert_eq!(detect_pubkey_format(&compressed), Option::Some(PubkeyFormat::Compressed));
+        assert_eq!(detect_pubkey_format(&secp256k1.negate_compressed(&compressed)), Option::Some(PubkeyFormat::Compressed));
+        assert_eq!(detect_pubkey_format(&uncompressed), Option::Some(PubkeyFormat::Uncompressed));
+        assert_eq!(detect_pubkey_format(&compressed[1..]), Option::Some(PubkeyFormat::XOnly));
+        assert_eq!(detect_pubkey_format(&[0x00]), Option::Some(PubkeyFormat::Identity));
+
+        // bogus lengths and prefixes
+        assert_eq!(detect_pubkey_format(&[]), None);
+        assert_eq!(detect_pubkey_format(&[0x01]), None);
+        assert_eq!(detect_pubkey_format(&compressed[..20]), None);
+        assert_eq!(detect_pubkey_format(&uncompressed[..64]), None);
+        let mut hybrid = uncompressed;
+        hybrid[0] = 0x06;
+        assert_eq!(detect_pubkey_format(&hybrid), None);
+        let mut bad_prefix = compressed;
+        bad_prefix[0] = 0x04;
+        assert_eq!(detect_pubkey_format(&bad_prefix), None);
+    }
+
+    #[test]
+    fn point_from_compressed_works_on_other_curves() {
+        let p256 = EllipticCurve::from_name("P-256").unwrap();
+        let sec = p256.generator.to_sec_compressed().unwrap();
+        assert_eq!(point_from_compressed(p256.curve, &sec), Ok(p256.generator));
+
+        let mut negated = sec;
+        negated[0] ^= 0x01;
+        let neg_g = point_from_compressed(p256.curve, &negated).unwrap();
+        assert_eq!(p256.generator + neg_g, Point::new(Identity, p256.curve));
+        negated[0] = 0x04;
+        assert_eq!(point_from_compressed(p256.curve, &negated), Err(ParseError::InvalidPrefix));
+    }
+
+    #[test]
+    fn from_sec_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+
+        // compressed G
+        let sec = hex_to_bytes("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert_eq!(secp256k1.from_sec(&sec), Ok(g));
+
+        // with the odd prefix it decodes to -G
+        let mut odd = sec.clone();
+        odd[0] = 0x03;
+        let neg_g = secp256k1.from_sec(&odd).unwrap();
+        assert_eq!(g + neg_g, Point::new(Identity, secp256k1.get_curve()));
+
+        // uncompressed G
+        let sec = hex_to_bytes(&format!("04{}{}", secp256k1.params().gx, secp256k1.params().gy)).unwrap();
+        assert_eq!(secp256k1.from_sec(&sec), Ok(g));
+
+        assert_eq!(secp256k1.from_sec(&[]), Err(ParseError::InvalidLength));
+        assert_eq!(secp256k1.from_sec(&sec[..33]), Err(ParseError::InvalidLength));
+        let mut bad = sec.clone();
+        bad[0] = 0x05;
+        assert_eq!(secp256k1.from_sec(&bad), Err(ParseError::InvalidPrefix));
+
+        // an x of p, or a y of p next to G's x, is out of range rather than off the curve
+        let p = hex_to_bytes(&secp256k1.params().p).unwrap();
+        let mut compressed = vec![0x02];
+        compressed.extend_from_slice(&p);
+        assert_eq!(secp256k1.from_sec(&compressed), Err(ParseError::OutOfRange));
+        let mut uncompressed = sec[..33].to_vec();
+        uncompressed.extend_from_slice(&p);
+        assert_eq!(secp256k1.from_sec(&uncompressed), Err(ParseError::OutOfRange));
+        // G's y with the low bit flipped is in range but off the curve
+        let mut off_curve = sec.clone();
+        off_curve[64] ^= 1;
+        assert_eq!(secp256k1.from_sec(&off_curve), Err(ParseError::NotOnCurve));
+    }
+
+    #[test]
+    fn to_sec_round_trip() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+
+        let sec = g.to_sec_compressed().unwrap();
+        assert_eq!(bytes_to_hex(&sec), format!("02{}", secp256k1.params().gx));
+        let sec = g.to_sec_uncompressed().unwrap();
+        assert_eq!(bytes_to_hex(&sec), format!("04{}{}", secp256k1.params().gx, secp256k1.params().gy));
+
+        for secret in [2u8, 3, 4, 5, 6] {
+            let point = secp256k1.get_public_key(U256::from(secret));
+            assert_eq!(secp256k1.from_sec(&point.to_sec_compressed().unwrap()), Ok(point));
+            assert_eq!(secp256k1.from_sec(&point.to_sec_uncompressed().unwrap()), Ok(point));
+        }
+
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert_eq!(zero.to_sec_compressed(), Err(EcError::Identity));
+    }
+
+    #[test]
+    fn report_works() {
+        let secp256k1 = SECP256K1::new();
+        let report = secp256k1.get_generator_point().report();
+        assert!(report.contains("compressed: 0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"), "{}", report);
+        assert!(report.contains("on curve:   true"), "{}", report);
+        assert!(report.contains("y^2 = x^3 + 0 x + 7 (mod 115792089237316195423570985008687907853269984665640564039457584007908834671663)"), "{}", report);
+        assert!(report.contains("x:          0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798 (55066263022277343669578718895168534326250603453777594175500187360389116729240)"), "{}", report);
+        assert_eq!(report.lines().count(), 5);
+
+        let zero = Point::new(Identity, secp256k1.get_curve()).report();
+        assert!(zero.contains("point:      identity"));
+        assert!(zero.contains("compressed: none"));
+    }
+
+    #[test]
+    fn eq_sec_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+
+        let compressed = hex_to_bytes("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert!(g.eq_sec(&compressed));
+        let uncompressed = hex_to_bytes(&format!("04{}{}", secp256k1.params().gx, secp256k1.params().gy)).unwrap();
+        assert!(g.eq_sec(&uncompressed));
+
+        // wrong parity, another point, a truncated encoding
+        let mut odd = compressed.clone();
+        odd[0] = 0x03;
+        assert!(!g.eq_sec(&odd));
+        assert!(!(g + g).eq_sec(&compressed));
+        assert!(!g.eq_sec(&compressed[..32]));
+
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert!(zero.eq_sec(&[0x00]));
+        assert!(!zero.eq_sec(&compressed));
+        assert!(!g.eq_sec(&[0x00]));
+    }
+
+    #[test]
+    fn point_from_coords_strict_works() {
+        let secp256k1 = SECP256K1::new();
+        let p = secp256k1.get_order();
+        let gx = U256::from_be_hex(&secp256k1.params().gx);
+        let gy = U256::from_be_hex(&secp256k1.params().gy);
+        assert_eq!(secp256k1.point_from_coords_strict(gx, gy), Ok(secp256k1.get_generator_point()));
+
+        // (1, y) is on the curve; p + 1 is 1 mod p, but isn't accepted as 1
+        let y = U256::from_be_hex("4218f20ae6c646b363db68605822fb14264ca8d2587fdd6fbc750d587e76a7ee");
+        assert!(secp256k1.point_from_coords_strict(U256::ONE, y).is_ok());
+        let p_plus_one = p.wrapping_add(&U256::ONE);
+        assert_eq!(secp256k1.point_from_coords_strict(p_plus_one, y), Err(EcError::OutOfRange));
+        assert_eq!(secp256k1.point_from_coords_strict(gx, p), Err(EcError::OutOfRange));
+        assert_eq!(secp256k1.point_from_coords_strict(p, gy), Err(EcError::OutOfRange));
+
+        // in range but off the curve
+        assert_eq!(secp256k1.point_from_coords_strict(gx, gy.wrapping_add(&U256::ONE)), Err(EcError::NotOnCurve));
+    }
+
+    #[test]
+    fn coords_from_hex_works() {
+        let secp256k1 = SECP256K1::new();
+        let coords = secp256k1.coords_from_hex(&secp256k1.params().gx, &secp256k1.params().gy);
+        assert_eq!(coords, Ok(secp256k1.get_generator_coords()));
+        assert!(secp256k1.get_curve().is_on_curve(&coords.unwrap()));
+
+        // short and odd-length hex is left padded
+        let p = secp256k1.get_order();
+        let small = Some(FieldElementBig::new(U256::from(0xabcu16), p), FieldElementBig::new(U256::from(7u8), p));
+        assert_eq!(secp256k1.coords_from_hex("abc", "7"), Ok(small));
+
+        assert_eq!(secp256k1.coords_from_hex("", "7"), Err(ParseError::InvalidLength));
+        assert_eq!(secp256k1.coords_from_hex(&"1".repeat(65), "7"), Err(ParseError::InvalidLength));
+        assert_eq!(secp256k1.coords_from_hex("0x12", "7"), Err(ParseError::InvalidHex));
+        assert_eq!(secp256k1.coords_from_hex(&secp256k1.params().p, "7"), Err(ParseError::OutOfRange));
+    }
+
+    #[test]
+    fn negate_compressed_works() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+
+        for secret in [U256::ONE, U256::from(2u8), U256::from(12345u16)] {
+            let point = secp256k1.get_public_key(secret);
+            let neg_point = secp256k1.get_public_key(n.wrapping_sub(&secret));
+            let sec = point.to_sec_compressed().unwrap();
+
+            let negated = secp256k1.negate_compressed(&sec);
+            assert_eq!(negated, neg_point.to_sec_compressed().unwrap());
+            assert_eq!(secp256k1.negate_compressed(&negated), sec);
+            assert!((secp256k1.from_sec(&negated).unwrap() + point).is_identity());
+        }
+    }
+
+    #[test]
+    fn y_from_x_works() {
+        let secp256k1 = SECP256K1::new();
+        let p = secp256k1.get_order();
+        let gx = U256::from_be_hex(&secp256k1.params().gx);
+        let gy = U256::from_be_hex(&secp256k1.params().gy);
+
+        // Gy is even, so the odd root is p - Gy
+        assert_eq!(secp256k1.y_from_x(gx, true), Option::Some(gy));
+        assert_eq!(secp256k1.y_from_x(gx, false), Option::Some(p.wrapping_sub(&gy)));
+
+        // x = 5 has no point: 5^3 + 7 = 132 is not a square mod p
+        assert_eq!(secp256k1.y_from_x(U256::from(5u8), true), None);
+        assert_eq!(secp256k1.y_from_x(p, true), None);
+    }
+
+    #[test]
+    fn both_ys_works() {
+        let secp256k1 = SECP256K1::new();
+        let p = secp256k1.get_order();
+        let curve = secp256k1.get_curve();
+        let gx = U256::from_be_hex(&secp256k1.params().gx);
+
+        let (even, odd) = secp256k1.both_ys(gx).unwrap();
+        assert_eq!(even, U256::from_be_hex(&secp256k1.params().gy));
+        assert!(!even.bit_vartime(0));
+        assert!(odd.bit_vartime(0));
+        // additive inverses mod p
+        assert_eq!(even.add_mod(&odd, &p), U256::ZERO);
+
+        let x = FieldElementBig::new(gx, p);
+        for y in [even, odd] {
+            assert!(curve.is_on_curve(&Some(x, FieldElementBig::new(y, p))));
+        }
+
+        assert_eq!(secp256k1.both_ys(U256::from(5u8)), None);
+    }
+
+    #[test]
+    fn point_from_x_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let gx = U256::from_be_hex(&secp256k1.params().gx);
+
+        assert_eq!(secp256k1.point_from_x(gx, true), Option::Some(g));
+        let neg_g = secp256k1.point_from_x(gx, false).unwrap();
+        assert!((g + neg_g).is_identity());
+        assert_eq!(secp256k1.point_from_x(U256::from(5u8), true), None);
+    }
+
+    #[test]
+    fn hex_works() {
+        assert_eq!(hex_to_bytes("00ff7a"), Ok(vec![0x00, 0xff, 0x7a]));
+        assert_eq!(bytes_to_hex(&[0x00, 0xff, 0x7a]), "00ff7a");
+        assert_eq!(hex_to_bytes("abc"), Err(ParseError::InvalidLength));
+        assert_eq!(hex_to_bytes("zz"), Err(ParseError::InvalidHex));
+        assert_eq!(hex_to_bytes("+f"), Err(ParseError::InvalidHex));
+        assert_eq!(hex_to_bytes("0-1f"), Err(ParseError::InvalidHex));
+    }
+
+    #[test]
+    fn raw_bytes_round_trip() {
+        let secp256k1 = SECP256K1::new();
+        let point = secp256k1.get_generator_point();
+
+        let raw: [u8; 64] = point.try_into().unwrap();
+        assert_eq!(&raw[..32], &U256::from_be_hex(&secp256k1.params().gx).to_be_bytes());
+        assert_eq!(Point::try_from(raw), Ok(point));
+
+        // identity has no raw encoding
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert_eq!(<[u8; 64]>::try_from(zero), Err(EcError::Identity));
+
+        // flipping a bit of y knocks it off the curve
+        let mut bad = raw;
+        bad[63] ^= 1;
+        assert_eq!(Point::try_from(bad), Err(EcError::NotOnCurve));
+
+        // x = p + 1 would reduce to the on-curve x = 1
+        let mut unreduced = [0u8; 64];
+        unreduced[..32].copy_from_slice(&secp256k1.get_order().wrapping_add(&U256::ONE).to_be_bytes());
+        unreduced[32..].copy_from_slice(&U256::from_be_hex("4218f20ae6c646b363db68605822fb14264ca8d2587fdd6fbc750d587e76a7ee").to_be_bytes());
+        assert_eq!(Point::try_from(unreduced), Err(EcError::OutOfRange));
+    }
+
+    #[test]
+    fn scalar_bytes_both_endiannesses() {
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+
+        let be = scalar_to_bytes_be(secret);
+        let le = scalar_to_bytes_le(secret);
+        assert_eq!(scalar_from_bytes_be(&be), secret);
+        assert_eq!(scalar_from_bytes_le(&le), secret);
+
+        // the same value, byte for byte reversed
+        assert_ne!(be, le);
+        assert_eq!((be[0], le[31]), (0xe3, 0xe3));
+        let mut reversed = be;
+        reversed.reverse();
+        assert_eq!(reversed, le);
+
+        // reading one order as the other gives a different number
+        assert_ne!(scalar_from_bytes_le(&be), secret);
+        assert_eq!(scalar_to_bytes_le(U256::ONE)[0], 1);
+        assert_eq!(scalar_to_bytes_be(U256::ONE)[31], 1);
+    }
+
+    #[test]
+    fn point_bytes_both_endiannesses() {
+        let secp256k1 = SECP256K1::new();
+        let point = secp256k1.get_public_key(U256::from(12345u16));
+
+        let be = point.to_bytes_be().unwrap();
+        let le = point.to_bytes_le().unwrap();
+        assert_eq!(secp256k1.point_from_bytes_be(&be), Ok(point));
+        assert_eq!(secp256k1.point_from_bytes_le(&le), Ok(point));
+
+        // each coordinate is reversed on its own, x stays first
+        assert_eq!(le[0], be[31]);
+        assert_eq!(le[32], be[63]);
+        assert_eq!(secp256k1.point_from_bytes_le(&be), Err(EcError::NotOnCurve));
+
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert_eq!(zero.to_bytes_le(), Err(EcError::Identity));
+
+        // x = p + 1 is 1 mod p, and (1, y) is on the curve, but the encoding
+        // isn't canonical
+        let y = U256::from_be_hex("4218f20ae6c646b363db68605822fb14264ca8d2587fdd6fbc750d587e76a7ee");
+        let x = secp256k1.get_order().wrapping_add(&U256::ONE);
+        let mut be = [0u8; 64];
+        be[..32].copy_from_slice(&x.to_be_bytes());
+        be[32..].copy_from_slice(&y.to_be_bytes());
+        let mut le = [0u8; 64];
+        le[..32].copy_from_slice(&x.to_le_bytes());
+        le[32..].copy_from_slice(&y.to_le_bytes());
+        assert_eq!(secp256k1.point_from_bytes_be(&be), Err(EcError::OutOfRange));
+        assert_eq!(secp256k1.point_from_bytes_le(&le), Err(EcError::OutOfRange));
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EcError {
+    // the coordinates don't satisfy y^2 = x^3 + ax + b
+    NotOnCurve,
+    // the two operands live on different curves
+    CurveMismatch,
+    // the identity was given where an affine point is required
+    Identity,
+    // curve parameters are unusable: the built-in constants fail a
+    // consistency check, a point's field elements don't share one nonzero
+    // prime, or a caller-supplied order is below 2
+    InvalidParameters,
+    // on the curve, but not in the subgroup the generator spans
+    NotInSubgroup,
+    // a coordinate is p or more rather than a reduced field element
+    OutOfRange,
+}
+
+impl fmt::Display for EcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EcError::NotOnCurve => write!(f, "point is not on the curve"),
+            EcError::CurveMismatch => write!(f, "points are on different curves"),
+            EcError::Identity => write!(f, "point is the identity"),
+            EcError::InvalidParameters => write!(f, "curve parameters are inconsistent"),
+            EcError::NotInSubgroup => write!(f, "point is not in the prime-order subgroup"),
+            EcError::OutOfRange => write!(f, "coordinate is not below the field prime"),
+        }
+    }
+}
+
+impl std::error::Error for EcError {}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    // the input has the wrong number of bytes (or hex digits)
+    InvalidLength,
+    // the leading byte is not a known SEC prefix
+    InvalidPrefix,
+    // the input isn't valid hex
+    InvalidHex,
+    // the decoded coordinates aren't a point on the curve
+    NotOnCurve,
+    // a decoded coordinate is not below the field prime
+    OutOfRange,
+    // the input looked like a DER signature but didn't decode as one
+    InvalidDer(DerError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidLength => write!(f, "invalid length"),
+            ParseError::InvalidPrefix => write!(f, "invalid prefix byte"),
+            ParseError::InvalidHex => write!(f, "invalid hex"),
+            ParseError::NotOnCurve => write!(f, "point is not on the curve"),
+            ParseError::OutOfRange => write!(f, "coordinate is not below the field prime"),
+            ParseError::InvalidDer(err) => write!(f, "invalid DER signature: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// no built-in curve goes by this name or OID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCurve(pub String);
+
+impl fmt::Display for UnknownCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown curve: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownCurve {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DerError {
+    // the input ends before a declared length
+    Truncated,
+    // a tag byte is not SEQUENCE (0x30) or INTEGER (0x02)
+    InvalidTag,
+    // a length is wrong, long-form, or leaves trailing bytes
+    InvalidLength,
+    // an integer is negative, zero-padded or wider than 256 bits
+    InvalidInteger,
+}
+
+impl fmt::Display for DerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DerError::Truncated => write!(f, "DER input is truncated"),
+            DerError::InvalidTag => write!(f, "unexpected DER tag"),
+            DerError::InvalidLength => write!(f, "invalid DER length"),
+            DerError::InvalidInteger => write!(f, "invalid DER integer"),
+        }
+    }
+}
+
+impl std::error::Error for DerError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MnemonicError {
+    // BIP39 sentences have 12, 15, 18, 21 or 24 words
+    InvalidWordCount(usize),
+    // the derived master key is 0 or >= n (astronomically unlikely)
+    InvalidMasterKey,
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MnemonicError::InvalidWordCount(count) => write!(f, "a mnemonic can't have {} words", count),
+            MnemonicError::InvalidMasterKey => write!(f, "the seed gives an invalid master key"),
+        }
+    }
+}
+
+impl std::error::Error for MnemonicError {}
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    // a secret key of 0 has the identity as its public key
+    Zero,
+    // the secret key is not below the group order n
+    OutOfRange,
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyError::Zero => write!(f, "secret key is zero"),
+            KeyError::OutOfRange => write!(f, "secret key is not below the group order"),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
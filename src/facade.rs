@@ -0,0 +1,201 @@
+//! ECDSA over plain byte arrays: 32-byte secrets and hashes, 33-byte
+//! compressed public keys and 64-byte r || s signatures, with no U256 or
+//! Point in sight. `CurveApi::new()` uses secp256k1, or P-256 when the
+//! `p256` feature is enabled; the types are the same either way.
+//!
+//! ```
+//! use elliptic_curve::prelude::*;
+//!
+//! let api = CurveApi::new();
+//! let secret = api.generate_secret_key();
+//! let public = api.public_key(&secret).unwrap();
+//!
+//! let hash = [0x42u8; 32];
+//! let signature = api.sign(&secret, &hash).unwrap();
+//! assert_eq!(api.verify(&public, &hash, &signature), Ok(true));
+//! assert_eq!(api.verify(&public, &[0u8; 32], &signature), Ok(false));
+//! ```
+//!
+//! Verification results are `#[must_use]`, so a dropped one is caught at
+//! compile time under `deny(unused_must_use)`:
+//!
+//! ```compile_fail
+//! #![deny(unused_must_use)]
+//! use elliptic_curve::prelude::*;
+//!
+//! let secp256k1 = SECP256K1::new();
+//! let g = secp256k1.get_generator_point();
+//! secp256k1.verify(g, U256::ONE, (U256::ONE, U256::ONE));
+//! ```
+
+use crate::curve_params::CurveParams;
+use crate::ecdsa::{check_keys, low_s, nonce_point, signature_from_compact, signature_s_mod_n, signature_to_compact, verify_scalars_mod_n};
+use crate::elliptic_curves_bigint::EllipticCurve;
+use crate::encoding::point_from_compressed;
+use crate::error::{KeyError, ParseError};
+use crate::secp256k1::random_scalar;
+use crate::window::WindowTable;
+use crypto_bigint::{Encoding, U256, rand_core::OsRng};
+
+// the façade's name before the curve became selectable; it follows the
+// features like CurveApi does
+pub type Secp256k1 = CurveApi;
+
+pub struct CurveApi {
+    params: CurveParams<4>,
+    // multiples of G, for key generation and signing
+    table: WindowTable<4>,
+}
+
+impl Default for CurveApi {
+    fn default() -> CurveApi {
+        CurveApi::new()
+    }
+}
+
+impl CurveApi {
+    // the curve selected by the secp256k1 / p256 features
+    pub fn new() -> CurveApi {
+        if cfg!(feature = "p256") {
+            CurveApi::p256()
+        } else {
+            CurveApi::secp256k1()
+        }
+    }
+
+    pub fn secp256k1() -> CurveApi {
+        CurveApi::from_params(EllipticCurve::from_name("secp256k1").expect("built-in curve"))
+    }
+
+    pub fn p256() -> CurveApi {
+        CurveApi::from_params(EllipticCurve::from_name("P-256").expect("built-in curve"))
+    }
+
+    fn from_params(params: CurveParams<4>) -> CurveApi {
+        let table = WindowTable::new(&params.generator);
+        CurveApi { params, table }
+    }
+
+    pub fn params(&self) -> &CurveParams<4> {
+        &self.params
+    }
+
+    // a random secret in [1, n), big-endian
+    #[must_use]
+    pub fn generate_secret_key(&self) -> [u8; 32] {
+        random_scalar(&mut OsRng, self.params.order).to_be_bytes()
+    }
+
+    // the compressed SEC1 public key of a big-endian secret
+    pub fn public_key(&self, secret: &[u8; 32]) -> Result<[u8; 33], KeyError> {
+        let secret = self.check_secret(secret)?;
+        let public = self.table.mul_ct(secret);
+        Ok(public.to_sec_compressed().expect("a valid secret never gives the identity"))
+    }
+
+    // deterministic (RFC 6979) low-s ECDSA over a 32-byte message hash
+    pub fn sign(&self, secret: &[u8; 32], message_hash: &[u8; 32]) -> Result<[u8; 64], KeyError> {
+        let secret = self.check_secret(secret)?;
+        let n = self.params.order;
+        let z = U256::from_be_bytes(*message_hash);
+        let (k, x, _) = nonce_point(&self.table, n, secret, z);
+        let r = x.wrapping_rem(&n);
+        let s = signature_s_mod_n(k, z, r, secret, n);
+        Ok(signature_to_compact(r, low_s(s, n)))
+    }
+
+    pub fn verify(&self, public_key: &[u8; 33], message_hash: &[u8; 32], signature: &[u8; 64]) -> Result<bool, ParseError> {
+        let public = point_from_compressed(self.params.curve, public_key)?;
+        let n = self.params.order;
+        let (r, s) = signature_from_compact(signature);
+        Ok(match verify_scalars_mod_n(U256::from_be_bytes(*message_hash), (r, s), n) {
+            Some((u1, u2)) => check_keys(&self.table, n, &[public], u1, u2, r)[0],
+            None => false,
+        })
+    }
+
+    fn check_secret(&self, secret: &[u8; 32]) -> Result<U256, KeyError> {
+        let secret = U256::from_be_bytes(*secret);
+        if secret == U256::ZERO {
+            return Err(KeyError::Zero);
+        }
+        if secret >= self.params.order {
+            return Err(KeyError::OutOfRange);
+        }
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::bytes_to_hex;
+
+    fn one() -> [u8; 32] {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        one
+    }
+
+    #[test]
+    fn curve_api_works() {
+        for api in [CurveApi::secp256k1(), CurveApi::p256()] {
+            let public = api.public_key(&one()).unwrap();
+            assert!(api.params().generator.eq_sec(&public));
+
+            assert_eq!(api.public_key(&[0u8; 32]), Err(KeyError::Zero));
+            assert_eq!(api.sign(&[0xffu8; 32], &[1u8; 32]), Err(KeyError::OutOfRange));
+
+            let secret = api.generate_secret_key();
+            let public = api.public_key(&secret).unwrap();
+            let signature = api.sign(&secret, &[7u8; 32]).unwrap();
+            assert_eq!(api.sign(&secret, &[7u8; 32]), Ok(signature));
+            assert_eq!(api.verify(&public, &[7u8; 32], &signature), Ok(true));
+            assert_eq!(api.verify(&public, &[8u8; 32], &signature), Ok(false));
+
+            // low s
+            let (_, s) = signature_from_compact(&signature);
+            assert!(s <= api.params().order >> 1);
+
+            let mut negated = public;
+            negated[0] ^= 0x01;
+            assert_eq!(api.verify(&negated, &[7u8; 32], &signature), Ok(false));
+            let mut bad = public;
+            bad[0] = 0x05;
+            assert_eq!(api.verify(&bad, &[7u8; 32], &signature), Err(ParseError::InvalidPrefix));
+        }
+    }
+
+    #[test]
+    fn secp256k1_signatures_match_the_curve_methods() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new();
+        let api = CurveApi::secp256k1();
+        let secret = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let z = U256::from_be_hex("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+
+        let signature = api.sign(&secret.to_be_bytes(), &z.to_be_bytes()).unwrap();
+        assert_eq!(signature[..], secp256k1.sign_recoverable_compact(secret, z)[..64]);
+    }
+
+    #[test]
+    fn secp256k1_name_still_works() {
+        let secp = Secp256k1::new();
+        let public = secp.public_key(&one()).unwrap();
+        assert!(secp.params().generator.eq_sec(&public));
+        assert_eq!(Secp256k1::default().public_key(&one()), Ok(public));
+    }
+
+    #[test]
+    #[cfg(not(feature = "p256"))]
+    fn default_curve_is_secp256k1() {
+        let public = CurveApi::new().public_key(&one()).unwrap();
+        assert_eq!(bytes_to_hex(&public), "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn default_curve_is_p256() {
+        let public = CurveApi::new().public_key(&one()).unwrap();
+        assert_eq!(bytes_to_hex(&public), "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+    }
+}
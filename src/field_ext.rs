@@ -0,0 +1,227 @@
+use finite_field::{FieldElement, FieldElementBig};
+use crypto_bigint::Uint;
+use std::ops::{Add, Mul, Rem, Sub, Shr};
+use num::{One, Num, Bounded};
+use std::fmt::Debug;
+
+pub trait PowU32 {
+    // x^e for a small fixed exponent, e.g. squaring and cubing in the curve equation
+    fn pow_u32(&self, e: u32) -> Self;
+}
+
+impl<T> PowU32 for FieldElement<T>
+    where T: Rem<Output = T> + Mul<Output = T> + Copy + Sub<Output = T> + Add<Output = T> + Shr<Output = T>,
+          T: One + Num + PartialOrd + Bounded + Debug,
+{
+    fn pow_u32(&self, e: u32) -> Self {
+        if e == 0 {
+            return self.pow(T::zero());
+        }
+        // square and multiply from the top set bit down
+        let top = 31 - e.leading_zeros();
+        let mut result = *self;
+        for i in (0..top).rev() {
+            result = result * result;
+            if (e >> i) & 1 == 1 {
+                result = result * *self;
+            }
+        }
+        result
+    }
+}
+
+impl<const LIMBS: usize> PowU32 for FieldElementBig<LIMBS> {
+    fn pow_u32(&self, e: u32) -> Self {
+        if e == 0 {
+            return self.pow(Uint::ZERO);
+        }
+        let top = 31 - e.leading_zeros();
+        let mut result = *self;
+        for i in (0..top).rev() {
+            result = result * result;
+            if (e >> i) & 1 == 1 {
+                result = result * *self;
+            }
+        }
+        result
+    }
+}
+
+// FieldElement comes from another crate, so std's Neg can't be
+// implemented for it here; this trait stands in.
+pub trait FieldNeg {
+    // the additive inverse (p - num) mod p
+    fn neg(&self) -> Self;
+}
+
+impl<T> FieldNeg for FieldElement<T>
+    where T: Rem<Output = T> + Mul<Output = T> + Copy + Sub<Output = T> + Add<Output = T> + Shr<Output = T>,
+          T: One + Num + PartialOrd + Bounded + Debug,
+{
+    fn neg(&self) -> Self {
+        let p = self.get_prime();
+        FieldElement::new((p - self.get_num()) % p, p)
+    }
+}
+
+impl<const LIMBS: usize> FieldNeg for FieldElementBig<LIMBS> {
+    fn neg(&self) -> Self {
+        let p = self.get_prime();
+        FieldElementBig::new(p.wrapping_sub(&self.get_num()).wrapping_rem(&p), p)
+    }
+}
+
+pub trait Sqrt: Sized {
+    // square root choosing the method from p mod 4
+    fn sqrt(&self) -> Option<Self>;
+    // shortcut r = n^((p+1)/4), only valid for p = 3 mod 4
+    fn sqrt_3mod4(&self) -> Option<Self>;
+    // Tonelli–Shanks, valid for any odd prime p
+    fn sqrt_ts(&self) -> Option<Self>;
+}
+
+impl<const LIMBS: usize> Sqrt for FieldElementBig<LIMBS> {
+    fn sqrt(&self) -> Option<Self> {
+        let p = self.get_prime();
+        let three = Uint::from(3u8);
+        if p & three == three {
+            self.sqrt_3mod4()
+        } else {
+            self.sqrt_ts()
+        }
+    }
+
+    fn sqrt_3mod4(&self) -> Option<Self> {
+        let p = self.get_prime();
+        // (p + 1) / 4 == p / 4 + 1 when p = 3 mod 4, and it can't overflow
+        let exp = (p >> 2).wrapping_add(&Uint::ONE);
+        let root = self.pow(exp);
+        if root * root == *self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    fn sqrt_ts(&self) -> Option<Self> {
+        let p = self.get_prime();
+        let zero = FieldElementBig::new(Uint::ZERO, p);
+        let one = FieldElementBig::new(Uint::ONE, p);
+        if *self == zero {
+            return Some(zero);
+        }
+
+        // Euler's criterion: n is a residue iff n^((p-1)/2) == 1
+        let p_minus_one = p.wrapping_sub(&Uint::ONE);
+        let half = p_minus_one >> 1;
+        if self.pow(half) != one {
+            return None;
+        }
+
+        // p - 1 = q * 2^s with q odd
+        let s = p_minus_one.trailing_zeros();
+        let q = p_minus_one >> s;
+
+        // find any quadratic non-residue z
+        let mut z = one + one;
+        while z.pow(half) == one {
+            z = z + one;
+        }
+
+        let mut m = s;
+        let mut c = z.pow(q);
+        let mut t = self.pow(q);
+        let mut r = self.pow((q >> 1).wrapping_add(&Uint::ONE));
+        while t != one {
+            // least i with t^(2^i) == 1
+            let mut i = 0;
+            let mut t2i = t;
+            while t2i != one {
+                t2i = t2i * t2i;
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b * b;
+            }
+            m = i;
+            c = b * b;
+            t = t * c;
+            r = r * b;
+        }
+
+        Some(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_bigint::U256;
+
+    #[test]
+    fn neg_works() {
+        for num in [0u16, 1, 47, 111, 112, 222] {
+            let x: FieldElement<u16> = FieldElement::new(num, 223);
+            let zero: FieldElement<u16> = FieldElement::new(0, 223);
+            assert_eq!(x + x.neg(), zero);
+            assert_eq!(x.neg().neg(), x);
+        }
+        let x: FieldElement<u16> = FieldElement::new(1, 223);
+        assert_eq!(x.neg(), FieldElement::new(222, 223));
+
+        let p = U256::from(223u8);
+        let x = FieldElementBig::new(U256::from(47u8), p);
+        assert_eq!(x.neg(), FieldElementBig::new(U256::from(176u8), p));
+        assert_eq!(FieldElementBig::new(U256::ZERO, p).neg(), FieldElementBig::new(U256::ZERO, p));
+    }
+
+    #[test]
+    fn pow_u32_works() {
+        let p = U256::from(223u8);
+        let x = FieldElementBig::new(U256::from(47u8), p);
+        assert_eq!(x.pow_u32(3), x.pow(U256::from(3u8)));
+        assert_eq!(x.pow_u32(2), x * x);
+        assert_eq!(x.pow_u32(1), x);
+        assert_eq!(x.pow_u32(0), x.pow(U256::ZERO));
+        assert_eq!(x.pow_u32(222), x.pow(U256::from(222u8)));
+
+        let y: FieldElement<u16> = FieldElement::new(47, 223);
+        assert_eq!(y.pow_u32(3), y.pow(3));
+        assert_eq!(y.pow_u32(10), y.pow(10));
+    }
+
+    #[test]
+    fn sqrt_ts_works() {
+        // 13 = 1 mod 4, so the shortcut doesn't apply
+        let p = U256::from(13u8);
+        let residues = [0u8, 1, 3, 4, 9, 10, 12];
+        for n in 0u8..13 {
+            let x = FieldElementBig::new(U256::from(n), p);
+            let root = x.sqrt_ts();
+            if residues.contains(&n) {
+                let r = root.unwrap();
+                assert_eq!(r * r, x);
+            } else {
+                assert_eq!(root, None);
+            }
+            // the dispatching sqrt must pick Tonelli–Shanks here
+            assert_eq!(x.sqrt(), root);
+        }
+    }
+
+    #[test]
+    fn sqrt_3mod4_agrees_with_ts() {
+        let p = U256::from(223u8);
+        for n in 0u8..223 {
+            let x = FieldElementBig::new(U256::from(n), p);
+            let fast = x.sqrt_3mod4();
+            let general = x.sqrt_ts();
+            assert_eq!(fast.is_some(), general.is_some());
+            if let (Some(a), Some(b)) = (fast, general) {
+                assert_eq!(a * a, b * b);
+            }
+        }
+    }
+}
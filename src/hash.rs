@@ -0,0 +1,102 @@
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use tiny_keccak::{Hasher, Keccak};
+
+pub fn sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    Sha256::digest(data.as_ref()).into()
+}
+
+// sha256(sha256(data)), as used for Bitcoin checksums and txids
+pub fn double_sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    sha256(sha256(data))
+}
+
+pub fn ripemd160(data: impl AsRef<[u8]>) -> [u8; 20] {
+    Ripemd160::digest(data.as_ref()).into()
+}
+
+// ripemd160(sha256(data)), the Bitcoin "pubkey hash"
+pub fn hash160(data: impl AsRef<[u8]>) -> [u8; 20] {
+    ripemd160(sha256(data))
+}
+
+// the original Keccak padding used by Ethereum, not NIST SHA3-256
+pub fn keccak256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut out = [0u8; 32];
+    hasher.update(data.as_ref());
+    hasher.finalize(&mut out);
+    out
+}
+
+// The digest a protocol signs, so sign_message / verify_message work for
+// Bitcoin, Ethereum or anything else with a 32-byte hash
+pub trait MessageHasher {
+    fn hash(&self, msg: &[u8]) -> [u8; 32];
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Sha256Hasher;
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Keccak256Hasher;
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DoubleSha256Hasher;
+
+impl MessageHasher for Sha256Hasher {
+    fn hash(&self, msg: &[u8]) -> [u8; 32] {
+        sha256(msg)
+    }
+}
+
+impl MessageHasher for Keccak256Hasher {
+    fn hash(&self, msg: &[u8]) -> [u8; 32] {
+        keccak256(msg)
+    }
+}
+
+impl MessageHasher for DoubleSha256Hasher {
+    fn hash(&self, msg: &[u8]) -> [u8; 32] {
+        double_sha256(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::bytes_to_hex;
+
+    #[test]
+    fn sha256_works() {
+        assert_eq!(bytes_to_hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(bytes_to_hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(bytes_to_hex(&double_sha256(b"")), "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456");
+    }
+
+    #[test]
+    fn ripemd160_works() {
+        assert_eq!(bytes_to_hex(&ripemd160(b"")), "9c1185a5c5e9fc54612808977ee8f548b2258d31");
+        assert_eq!(bytes_to_hex(&ripemd160(b"abc")), "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+        assert_eq!(bytes_to_hex(&hash160(b"")), "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb");
+    }
+
+    #[test]
+    fn any_byte_container_works() {
+        let expected = sha256(b"abc");
+        let text = String::from("abc");
+        assert_eq!(sha256(vec![0x61u8, 0x62, 0x63]), expected);
+        assert_eq!(sha256(&[0x61u8, 0x62, 0x63][..]), expected);
+        assert_eq!(sha256([0x61u8, 0x62, 0x63]), expected);
+        assert_eq!(sha256(text.as_bytes()), expected);
+        assert_eq!(sha256(&text), expected);
+        assert_eq!(sha256("abc"), expected);
+        assert_eq!(keccak256(text.clone()), keccak256(b"abc"));
+        assert_eq!(hash160(Vec::<u8>::new()), hash160(b""));
+    }
+
+    #[test]
+    fn keccak256_works() {
+        assert_eq!(bytes_to_hex(&keccak256(b"")), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+    }
+}
@@ -0,0 +1,127 @@
+// secp256k1 keys that serialize as hex strings, for config files and JSON:
+// a secret as 64 hex digits, a public key as 66 hex digits of compressed
+// SEC1. Deserializing checks the key is usable, not just well-formed.
+use crate::encoding::{bytes_to_hex, hex_to_bytes};
+use crate::elliptic_curves_bigint::Point;
+use crate::error::{KeyError, ParseError};
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::{Encoding, U256};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SecretKeyHex(U256);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PublicKeyHex(Point<4>);
+
+impl SecretKeyHex {
+    // a secret in [1, n)
+    pub fn new(secret: U256) -> Result<SecretKeyHex, KeyError> {
+        SECP256K1::new().try_public_key(secret)?;
+        Ok(SecretKeyHex(secret))
+    }
+
+    pub fn secret(&self) -> U256 {
+        self.0
+    }
+}
+
+impl PublicKeyHex {
+    // a non-identity point on secp256k1
+    pub fn new(pubkey: Point<4>) -> Result<PublicKeyHex, ParseError> {
+        SECP256K1::new().validate_public_key(&pubkey).map_err(|_| ParseError::NotOnCurve)?;
+        Ok(PublicKeyHex(pubkey))
+    }
+
+    pub fn public_key(&self) -> Point<4> {
+        self.0
+    }
+}
+
+impl Serialize for SecretKeyHex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&bytes_to_hex(&self.0.to_be_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKeyHex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SecretKeyHex, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        let bytes = hex_to_bytes(&hex).map_err(D::Error::custom)?;
+        if bytes.len() != 32 {
+            return Err(D::Error::custom(ParseError::InvalidLength));
+        }
+        SecretKeyHex::new(U256::from_be_slice(&bytes)).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for PublicKeyHex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let sec = self.0.to_sec_compressed().expect("PublicKeyHex is never the identity");
+        serializer.serialize_str(&bytes_to_hex(&sec))
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKeyHex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<PublicKeyHex, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        let bytes = hex_to_bytes(&hex).map_err(D::Error::custom)?;
+        if bytes.len() != 33 {
+            return Err(D::Error::custom(ParseError::InvalidLength));
+        }
+        let pubkey = SECP256K1::new().from_sec(&bytes).map_err(D::Error::custom)?;
+        PublicKeyHex::new(pubkey).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_json_round_trip_works() {
+        let secp256k1 = SECP256K1::new();
+        let secret = SecretKeyHex::new(U256::ONE).unwrap();
+        let public = PublicKeyHex::new(secp256k1.get_generator_point()).unwrap();
+
+        let json = serde_json::to_string(&(secret, public)).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"["0000000000000000000000000000000000000000000000000000000000000001","#,
+                r#""0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"]"#
+            )
+        );
+        let (secret2, public2): (SecretKeyHex, PublicKeyHex) = serde_json::from_str(&json).unwrap();
+        assert_eq!(secret2, secret);
+        assert_eq!(public2, public);
+
+        let random = SecretKeyHex::new(secp256k1.get_secret_key()).unwrap();
+        let json = serde_json::to_string(&random).unwrap();
+        assert_eq!(serde_json::from_str::<SecretKeyHex>(&json).unwrap(), random);
+    }
+
+    #[test]
+    fn deserializing_rejects_bad_keys() {
+        // zero, n, wrong length, not hex
+        let n = format!("\"{}\"", SECP256K1::new().params().n);
+        for json in [
+            "\"0000000000000000000000000000000000000000000000000000000000000000\"".to_owned(),
+            n,
+            "\"01\"".to_owned(),
+            "\"zz00000000000000000000000000000000000000000000000000000000000001\"".to_owned(),
+        ] {
+            assert!(serde_json::from_str::<SecretKeyHex>(&json).is_err(), "{}", json);
+        }
+
+        // x = 5 is not the x of any secp256k1 point, and 0x05 is no SEC1 prefix
+        for json in [
+            "\"020000000000000000000000000000000000000000000000000000000000000005\"",
+            "\"0579be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\"",
+            "\"0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\"",
+        ] {
+            assert!(serde_json::from_str::<PublicKeyHex>(json).is_err(), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use finite_field::FieldElementBig;
+use crypto_bigint::Uint;
+use crypto_bigint::subtle::{Choice, ConstantTimeEq};
+use crate::elliptic_curves_bigint::{bits_msb_first, Coords, EllipticCurve, Point};
+use Coords::{Some, Identity};
+
+// (X, Y, Z) stands for the affine point (X / Z^2, Y / Z^3); Z == 0 is the identity.
+// Adding and doubling here needs no field division, only one at to_affine.
+#[derive(Debug, Copy, Clone)]
+pub struct JacobianPoint<const LIMBS: usize> {
+    pub x: FieldElementBig<LIMBS>,
+    pub y: FieldElementBig<LIMBS>,
+    pub z: FieldElementBig<LIMBS>,
+    pub curve: EllipticCurve<LIMBS>,
+}
+
+impl<const LIMBS: usize> JacobianPoint<LIMBS> {
+    pub fn identity(curve: EllipticCurve<LIMBS>) -> JacobianPoint<LIMBS> {
+        let p = curve.a.get_prime();
+        let one = FieldElementBig::new(Uint::ONE, p);
+        let zero = FieldElementBig::new(Uint::ZERO, p);
+        JacobianPoint {
+            x: one,
+            y: one,
+            z: zero,
+            curve
+        }
+    }
+
+    pub fn from_affine(point: &Point<LIMBS>) -> JacobianPoint<LIMBS> {
+        if let Some(x, y) = point.coords {
+            let one = FieldElementBig::new(Uint::ONE, point.curve.a.get_prime());
+            JacobianPoint {
+                x,
+                y,
+                z: one,
+                curve: point.curve
+            }
+        } else {
+            JacobianPoint::identity(point.curve)
+        }
+    }
+
+    pub fn to_affine(&self) -> Point<LIMBS> {
+        if self.is_identity() {
+            return Point { coords: Identity, curve: self.curve };
+        }
+        let one = FieldElementBig::new(Uint::ONE, self.curve.a.get_prime());
+        let z_inv = one / self.z;
+        let z_inv2 = z_inv * z_inv;
+        let x = self.x * z_inv2;
+        let y = self.y * z_inv2 * z_inv;
+        Point { coords: Some(x, y), curve: self.curve }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.get_num() == Uint::ZERO
+    }
+
+    // Z == 0, compared limb by limb without branching
+    pub fn ct_is_identity(&self) -> Choice {
+        self.z.get_num().ct_eq(&Uint::ZERO)
+    }
+
+    pub fn double(&self) -> JacobianPoint<LIMBS> {
+        if self.is_identity() || self.y.get_num() == Uint::ZERO {
+            return JacobianPoint::identity(self.curve);
+        }
+        let xx = self.x * self.x;
+        let yy = self.y * self.y;
+        let yyyy = yy * yy;
+        let zz = self.z * self.z;
+
+        // S = 4 X Y^2, M = 3 X^2 + a Z^4
+        let xyy = self.x * yy;
+        let s = (xyy + xyy) + (xyy + xyy);
+        let m = xx + xx + xx + self.curve.a * zz * zz;
+
+        let x3 = m * m - s - s;
+        let yyyy2 = yyyy + yyyy;
+        let yyyy8 = (yyyy2 + yyyy2) + (yyyy2 + yyyy2);
+        let y3 = m * (s - x3) - yyyy8;
+        let yz = self.y * self.z;
+        let z3 = yz + yz;
+
+        JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+            curve: self.curve
+        }
+    }
+
+    pub fn add(&self, rhs: &JacobianPoint<LIMBS>) -> JacobianPoint<LIMBS> {
+        assert_eq!(self.curve, rhs.curve);
+        if self.is_identity() {
+            return *rhs;
+        }
+        if rhs.is_identity() {
+            return *self;
+        }
+        let z1z1 = self.z * self.z;
+        let z2z2 = rhs.z * rhs.z;
+        let u1 = self.x * z2z2;
+        let u2 = rhs.x * z1z1;
+        let s1 = self.y * rhs.z * z2z2;
+        let s2 = rhs.y * self.z * z1z1;
+        self.add_inner(u1, u2, s1, s2, self.z * rhs.z)
+    }
+
+    // Jacobian + affine: with Z2 = 1 the Z2 powers drop out, saving
+    // several multiplications over add.
+    pub fn add_affine(&self, affine: &Point<LIMBS>) -> JacobianPoint<LIMBS> {
+        assert_eq!(self.curve, affine.curve);
+        if let Some(x2, y2) = affine.coords {
+            if self.is_identity() {
+                return JacobianPoint::from_affine(affine);
+            }
+            let z1z1 = self.z * self.z;
+            let u2 = x2 * z1z1;
+            let s2 = y2 * self.z * z1z1;
+            self.add_inner(self.x, u2, self.y, s2, self.z)
+        } else {
+            *self
+        }
+    }
+
+    // shared tail of add/add_affine given U1, U2, S1, S2 and Z1 * Z2
+    fn add_inner(
+        &self,
+        u1: FieldElementBig<LIMBS>,
+        u2: FieldElementBig<LIMBS>,
+        s1: FieldElementBig<LIMBS>,
+        s2: FieldElementBig<LIMBS>,
+        z1z2: FieldElementBig<LIMBS>,
+    ) -> JacobianPoint<LIMBS> {
+        if u1 == u2 {
+            if s1 == s2 {
+                return self.double();
+            }
+            return JacobianPoint::identity(self.curve);
+        }
+        let h = u2 - u1;
+        let r = s2 - s1;
+        let hh = h * h;
+        let hhh = h * hh;
+        let v = u1 * hh;
+        let x3 = r * r - hhh - v - v;
+        let y3 = r * (v - x3) - s1 * hhh;
+        let z3 = z1z2 * h;
+        JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+            curve: self.curve
+        }
+    }
+
+    // MSB-first double-and-add, so the addend is always the fixed affine
+    // point and every addition can be a mixed one.
+    pub fn mul_affine(scalar: Uint<LIMBS>, point: &Point<LIMBS>) -> Point<LIMBS> {
+        let mut result = JacobianPoint::identity(point.curve);
+        for bit in bits_msb_first(scalar) {
+            result = result.double();
+            if bit {
+                result = result.add_affine(point);
+            }
+        }
+        result.to_affine()
+    }
+}
+
+// Names matching the k256 crate, to ease migrating from it
+pub type AffinePoint = Point<4>;
+pub type ProjectivePoint = JacobianPoint<4>;
+
+impl<const LIMBS: usize> Point<LIMBS> {
+    pub fn to_projective(&self) -> JacobianPoint<LIMBS> {
+        JacobianPoint::from_affine(self)
+    }
+}
+
+impl<const LIMBS: usize> From<Point<LIMBS>> for JacobianPoint<LIMBS> {
+    fn from(point: Point<LIMBS>) -> JacobianPoint<LIMBS> {
+        JacobianPoint::from_affine(&point)
+    }
+}
+
+impl<const LIMBS: usize> From<JacobianPoint<LIMBS>> for Point<LIMBS> {
+    fn from(point: JacobianPoint<LIMBS>) -> Point<LIMBS> {
+        point.to_affine()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::SECP256K1;
+    use crypto_bigint::U256;
+
+    #[test]
+    fn mixed_addition_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let g3 = U256::from(3u8) * g;
+
+        // a Jacobian point with Z != 1
+        let jg2 = JacobianPoint::from_affine(&g).double();
+        let jg3 = JacobianPoint::from_affine(&g3);
+
+        let mixed = jg2.add_affine(&g3);
+        let full = jg2.add(&jg3);
+        assert_eq!(mixed.to_affine(), full.to_affine());
+        assert_eq!(mixed.to_affine(), U256::from(5u8) * g);
+
+        // doubling through add_affine and adding an inverse
+        let jg = JacobianPoint::from_affine(&g);
+        assert_eq!(jg.add_affine(&g).to_affine(), g + g);
+        let neg_g = (secp256k1.get_group_order().wrapping_sub(&U256::ONE)) * g;
+        assert!(jg.add_affine(&neg_g).is_identity());
+    }
+
+    #[test]
+    fn affine_projective_conversions_work() {
+        let secp256k1 = SECP256K1::new();
+        let g: AffinePoint = secp256k1.get_generator_point();
+
+        let projective: ProjectivePoint = g.to_projective();
+        assert_eq!(projective.to_affine(), g);
+        assert_eq!(AffinePoint::from(ProjectivePoint::from(g)), g);
+
+        // a projective point with Z != 1 survives the round trip too
+        let doubled = projective.double();
+        let affine: AffinePoint = doubled.into();
+        assert_eq!(affine, g + g);
+        assert_eq!(affine.to_projective().to_affine(), affine);
+
+        // and so does the identity
+        let zero = Point::new(Identity, secp256k1.get_curve());
+        assert!(zero.to_projective().is_identity());
+        assert_eq!(zero.to_projective().to_affine(), zero);
+    }
+
+    #[test]
+    fn ct_is_identity_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = JacobianPoint::from_affine(&secp256k1.get_generator_point());
+        let zero = JacobianPoint::identity(secp256k1.get_curve());
+        for point in [g, g.double(), zero, zero.double(), g.add(&zero)] {
+            assert_eq!(bool::from(point.ct_is_identity()), point.is_identity());
+            assert_eq!(bool::from(point.ct_is_identity()), point.to_affine().is_identity());
+        }
+    }
+
+    #[test]
+    fn mul_affine_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let k = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        assert_eq!(JacobianPoint::mul_affine(k, &g), k * g);
+        assert_eq!(JacobianPoint::mul_affine(secp256k1.get_group_order(), &g).coords, Identity);
+    }
+}
@@ -1,3 +1,24 @@
 pub mod elliptic_curves;
 pub mod secp256k1;
-pub mod elliptic_curves_bigint;
\ No newline at end of file
+pub mod elliptic_curves_bigint;
+pub mod field_ext;
+pub mod address;
+pub mod error;
+pub mod jacobian;
+pub mod encoding;
+pub mod scalar256;
+pub mod ecdsa;
+pub mod curve_params;
+pub mod multisig;
+pub mod hash;
+pub mod mnemonic;
+pub mod facade;
+pub mod prelude;
+pub mod ecdh;
+pub mod msm;
+pub mod window;
+#[cfg(feature = "rand")]
+pub mod sampling;
+#[cfg(feature = "serde")]
+pub mod hex_keys;
+pub mod swu;
\ No newline at end of file
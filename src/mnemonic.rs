@@ -0,0 +1,79 @@
+use crate::error::MnemonicError;
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::U256;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+// BIP39 seed: PBKDF2-HMAC-SHA512 over the sentence with salt "mnemonic" + passphrase,
+// 2048 rounds. Words are re-joined with single spaces; inputs are expected to be
+// NFKD already (true for the ASCII English wordlist), no normalization is done here.
+pub fn mnemonic_to_seed(words: &str, passphrase: &str) -> Result<[u8; 64], MnemonicError> {
+    let words: Vec<&str> = words.split_whitespace().collect();
+    if ![12, 15, 18, 21, 24].contains(&words.len()) {
+        return Err(MnemonicError::InvalidWordCount(words.len()));
+    }
+    let sentence = words.join(" ");
+    let salt = format!("mnemonic{}", passphrase);
+
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(sentence.as_bytes(), salt.as_bytes(), 2048, &mut seed);
+    Ok(seed)
+}
+
+impl SECP256K1 {
+    // BIP32 master key: the left half of HMAC-SHA512("Bitcoin seed", seed),
+    // which must be a valid secret (0 < k < n)
+    pub fn master_secret_from_seed(&self, seed: &[u8]) -> Result<U256, MnemonicError> {
+        let mut mac = Hmac::<Sha512>::new_from_slice(b"Bitcoin seed").expect("HMAC accepts any key length");
+        mac.update(seed);
+        let digest = mac.finalize().into_bytes();
+
+        let secret = U256::from_be_slice(&digest[..32]);
+        if secret == U256::ZERO || secret >= self.get_group_order() {
+            return Err(MnemonicError::InvalidMasterKey);
+        }
+        Ok(secret)
+    }
+
+    pub fn secret_from_mnemonic(&self, words: &str, passphrase: &str) -> Result<U256, MnemonicError> {
+        let seed = mnemonic_to_seed(words, passphrase)?;
+        self.master_secret_from_seed(&seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{bytes_to_hex, hex_to_bytes};
+
+    const ABANDON: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn mnemonic_to_seed_works() {
+        // the first BIP39 test vector, passphrase "TREZOR"
+        let seed = mnemonic_to_seed(ABANDON, "TREZOR").unwrap();
+        assert_eq!(
+            bytes_to_hex(&seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        // extra whitespace doesn't matter, the word count does
+        assert_eq!(mnemonic_to_seed(&format!("  {}\n", ABANDON.replace(' ', "  ")), "TREZOR"), Ok(seed));
+        assert_eq!(mnemonic_to_seed("abandon about", ""), Err(MnemonicError::InvalidWordCount(2)));
+    }
+
+    #[test]
+    fn master_secret_works() {
+        // BIP32 test vector 1
+        let secp256k1 = SECP256K1::new();
+        let seed = hex_to_bytes("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            secp256k1.master_secret_from_seed(&seed),
+            Ok(U256::from_be_hex("e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"))
+        );
+
+        let secret = secp256k1.secret_from_mnemonic(ABANDON, "TREZOR").unwrap();
+        let seed = mnemonic_to_seed(ABANDON, "TREZOR").unwrap();
+        assert_eq!(secp256k1.master_secret_from_seed(&seed), Ok(secret));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::elliptic_curves_bigint::{Coords, Point};
+use crate::jacobian::JacobianPoint;
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::U256;
+
+// Sum of k_i * P_i by Pippenger's bucket method. The scalars are cut into
+// c-bit windows; per window each point is dropped into the bucket of its
+// digit, and the buckets are combined with a running sum, so a window
+// costs about n + 2^(c+1) additions instead of n scalar-mul steps.
+// An empty slice gives the secp256k1 identity.
+pub fn multi_scalar_mul(pairs: &[(U256, Point<4>)]) -> Point<4> {
+    let Some((_, first)) = pairs.first() else {
+        return Point::new(Coords::Identity, SECP256K1::new().get_curve());
+    };
+    let curve = first.curve;
+    let c = window_bits(pairs.len());
+    let windows = U256::BITS.div_ceil(c);
+
+    let mut result = JacobianPoint::identity(curve);
+    for w in (0..windows).rev() {
+        for _ in 0..c {
+            result = result.double();
+        }
+
+        let mut buckets = vec![JacobianPoint::identity(curve); (1 << c) - 1];
+        for (scalar, point) in pairs {
+            let digit = window_digit(scalar, w * c, c);
+            if digit > 0 {
+                buckets[digit - 1] = buckets[digit - 1].add_affine(point);
+            }
+        }
+
+        // sum_j j * B_j as B_top + (B_top + B_top-1) + ...
+        let mut running = JacobianPoint::identity(curve);
+        let mut window_sum = JacobianPoint::identity(curve);
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket);
+            window_sum = window_sum.add(&running);
+        }
+        result = result.add(&window_sum);
+    }
+    result.to_affine()
+}
+
+// roughly log2(n), the usual Pippenger choice
+fn window_bits(n: usize) -> usize {
+    match n {
+        0..=1 => 1,
+        _ => ((usize::BITS - n.leading_zeros()) as usize).min(16),
+    }
+}
+
+// bits [start, start + width) of scalar as a number
+fn window_digit(scalar: &U256, start: usize, width: usize) -> usize {
+    (0..width)
+        .filter(|i| start + i < U256::BITS && scalar.bit_vartime(start + i))
+        .fold(0, |digit, i| digit | (1 << i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_sum(secp256k1: &SECP256K1, pairs: &[(U256, Point<4>)]) -> Point<4> {
+        pairs.iter().fold(Point::new(Coords::Identity, secp256k1.get_curve()), |sum, (k, p)| sum + *k * *p)
+    }
+
+    #[test]
+    fn multi_scalar_mul_matches_naive_sum() {
+        let secp256k1 = SECP256K1::new();
+        let pairs: Vec<(U256, Point<4>)> = (0..12)
+            .map(|_| (secp256k1.get_secret_key(), secp256k1.get_public_key(secp256k1.get_secret_key())))
+            .collect();
+
+        assert_eq!(multi_scalar_mul(&pairs), naive_sum(&secp256k1, &pairs));
+        for len in [1, 2, 3, 5] {
+            assert_eq!(multi_scalar_mul(&pairs[..len]), naive_sum(&secp256k1, &pairs[..len]));
+        }
+    }
+
+    #[test]
+    fn multi_scalar_mul_edge_cases() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let zero = Point::new(Coords::Identity, secp256k1.get_curve());
+        let n = secp256k1.get_group_order();
+
+        assert_eq!(multi_scalar_mul(&[]), zero);
+        assert_eq!(multi_scalar_mul(&[(U256::from(5u8), g)]), U256::from(5u8) * g);
+        assert_eq!(multi_scalar_mul(&[(U256::from(5u8), zero)]), zero);
+        assert_eq!(multi_scalar_mul(&[(U256::ZERO, g)]), zero);
+
+        // G + (n - 1) G = O, and identities among the points are skipped
+        let pairs = [(U256::ONE, g), (n.wrapping_sub(&U256::ONE), g), (U256::from(9u8), zero)];
+        assert_eq!(multi_scalar_mul(&pairs), zero);
+
+        // the same point twice lands in one bucket, which then doubles
+        let pairs = [(U256::from(3u8), g), (U256::from(3u8), g), (U256::MAX, g)];
+        assert_eq!(multi_scalar_mul(&pairs), naive_sum(&secp256k1, &pairs));
+    }
+}
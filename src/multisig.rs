@@ -0,0 +1,92 @@
+use crate::elliptic_curves_bigint::{Coords, Point};
+use crate::error::EcError;
+
+// Plain key aggregation P1 + P2 + ... + Pk (not MuSig, so no protection
+// against rogue-key attacks). Every key must be a non-identity point on
+// the first key's curve, and the sum must not be the identity.
+pub fn aggregate_pubkeys(keys: &[Point<4>]) -> Result<Point<4>, EcError> {
+    let first = keys.first().ok_or(EcError::Identity)?;
+    let mut aggregate = Point::new(Coords::Identity, first.curve);
+    for key in keys {
+        if key.is_identity() {
+            return Err(EcError::Identity);
+        }
+        if !first.curve.contains(key) {
+            return Err(if key.curve != first.curve { EcError::CurveMismatch } else { EcError::NotOnCurve });
+        }
+        aggregate = aggregate.checked_add(*key)?;
+    }
+    if aggregate.is_identity() {
+        return Err(EcError::Identity);
+    }
+    Ok(aggregate)
+}
+
+// Canonical key order for schemes that need one: lexicographic by
+// compressed SEC1 bytes. The identity has no encoding and sorts first.
+pub fn sort_pubkeys(keys: &mut [Point<4>]) {
+    keys.sort_by_cached_key(|key| key.to_sec_compressed().ok());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve_params::CurveParams;
+    use crate::elliptic_curves_bigint::EllipticCurve;
+    use crate::secp256k1::SECP256K1;
+    use crypto_bigint::U256;
+
+    #[test]
+    fn aggregate_pubkeys_works() {
+        let secp256k1 = SECP256K1::new();
+        let keys: Vec<Point<4>> = [3u8, 5, 11]
+            .iter()
+            .map(|k| secp256k1.get_public_key(U256::from(*k)))
+            .collect();
+
+        assert_eq!(aggregate_pubkeys(&keys), Ok(keys[0] + keys[1] + keys[2]));
+        assert_eq!(aggregate_pubkeys(&keys), Ok(secp256k1.get_public_key(U256::from(19u8))));
+    }
+
+    #[test]
+    fn sort_pubkeys_works() {
+        let secp256k1 = SECP256K1::new();
+        let keys: Vec<Point<4>> = (1u8..=6).map(|k| secp256k1.get_public_key(U256::from(k))).collect();
+
+        let mut sorted = keys.clone();
+        sort_pubkeys(&mut sorted);
+        let encoded: Vec<[u8; 33]> = sorted.iter().map(|key| key.to_sec_compressed().unwrap()).collect();
+        assert!(encoded.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // any input order sorts to the same list
+        let mut reversed: Vec<Point<4>> = keys.iter().rev().copied().collect();
+        sort_pubkeys(&mut reversed);
+        assert_eq!(reversed, sorted);
+        let mut rotated = keys.clone();
+        rotated.rotate_left(2);
+        sort_pubkeys(&mut rotated);
+        assert_eq!(rotated, sorted);
+        assert_eq!(aggregate_pubkeys(&rotated), aggregate_pubkeys(&keys));
+
+        // 0x02 keys (even y) come before 0x03 keys
+        let first_odd = encoded.iter().position(|key| key[0] == 0x03).unwrap_or(encoded.len());
+        assert!(encoded[first_odd..].iter().all(|key| key[0] == 0x03));
+    }
+
+    #[test]
+    fn aggregate_pubkeys_rejects_bad_input() {
+        let secp256k1 = SECP256K1::new();
+        let key = secp256k1.get_public_key(U256::from(3u8));
+
+        // a key from another curve
+        let p256: CurveParams<4> = EllipticCurve::from_name("prime256v1").unwrap();
+        assert_eq!(aggregate_pubkeys(&[key, p256.generator]), Err(EcError::CurveMismatch));
+
+        // the identity, an empty list, and keys that cancel out
+        let zero = Point::new(Coords::Identity, secp256k1.get_curve());
+        assert_eq!(aggregate_pubkeys(&[key, zero]), Err(EcError::Identity));
+        assert_eq!(aggregate_pubkeys(&[]), Err(EcError::Identity));
+        let neg_key = secp256k1.get_public_key(secp256k1.get_group_order().wrapping_sub(&U256::from(3u8)));
+        assert_eq!(aggregate_pubkeys(&[key, neg_key]), Err(EcError::Identity));
+    }
+}
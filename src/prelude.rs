@@ -0,0 +1,9 @@
+// The types most callers need, for a single `use elliptic_curve::prelude::*`
+pub use crate::curve_params::{CurveParams, DynCurve};
+pub use crate::elliptic_curves_bigint::{Coords, EllipticCurve, Point};
+pub use crate::error::{DerError, EcError, KeyError, ParseError};
+pub use crate::facade::{CurveApi, Secp256k1};
+pub use crate::scalar256::Scalar256;
+pub use crate::secp256k1::SECP256K1;
+pub use crypto_bigint::U256;
+pub use finite_field::FieldElementBig;
@@ -0,0 +1,68 @@
+// Random subgroup points through the rand crate, for randomized tests:
+//
+//     let point: Point<4> = rng.sample(&secp256k1);
+//
+// A uniform scalar in [1, n) times G is a uniform non-identity element of
+// the subgroup G generates.
+use crate::curve_params::CurveParams;
+use crate::elliptic_curves_bigint::Point;
+use crate::secp256k1::{random_scalar, SECP256K1};
+use crate::window::WindowTable;
+use rand::distributions::Distribution;
+use rand::Rng;
+
+impl Distribution<Point<4>> for SECP256K1 {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<4> {
+        let scalar = random_scalar(rng, self.get_group_order());
+        self.g_table().mul_vartime(scalar)
+    }
+}
+
+impl Distribution<Point<4>> for CurveParams<4> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<4> {
+        let scalar = random_scalar(rng, self.order);
+        WindowTable::new(&self.generator).mul_vartime(scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve_params::toy_params;
+    use crate::elliptic_curves_bigint::EllipticCurve;
+    use crypto_bigint::U256;
+
+    #[test]
+    fn sampled_points_are_on_the_curve() {
+        let mut rng = rand::thread_rng();
+        let secp256k1 = SECP256K1::new();
+        let points: Vec<Point<4>> = (0..8).map(|_| rng.sample(&secp256k1)).collect();
+        for point in &points {
+            assert!(secp256k1.get_curve().contains(point));
+            assert!(!point.is_identity());
+        }
+        assert_ne!(points[0], points[1]);
+
+        let p256 = EllipticCurve::from_name("P-256").unwrap();
+        for point in (&p256).sample_iter(&mut rng).take(4) {
+            assert!(p256.curve.contains(&point));
+        }
+    }
+
+    #[test]
+    fn sampling_works_for_a_small_order() {
+        // y^2 = x^3 + 7 over F_223 with G = (47, 71) of order 21; unmasked
+        // 256-bit draws would essentially never fall below 21
+        let mut rng = rand::thread_rng();
+        let n = U256::from(21u8);
+        let scalars: Vec<U256> = (0..200).map(|_| random_scalar(&mut rng, n)).collect();
+        assert!(scalars.iter().all(|k| *k != U256::ZERO && *k < n));
+        assert!(scalars.iter().any(|k| *k != scalars[0]));
+
+        let toy: CurveParams<4> = toy_params();
+        for point in (&toy).sample_iter(&mut rng).take(20) {
+            assert!(toy.is_in_subgroup(&point));
+            assert!(!point.is_identity());
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::elliptic_curves_bigint::Point;
+use crate::secp256k1::SECP256K1;
+use crypto_bigint::{Encoding, U256};
+use crypto_bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use num_bigint::BigUint;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// the secp256k1 group order n
+const N: U256 = U256::from_be_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+
+// An integer mod n, the scalar field of secp256k1. Keeping this separate
+// from FieldElementBig (mod p) stops the two moduli from being mixed up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Scalar256(U256);
+
+impl Scalar256 {
+    pub const ZERO: Scalar256 = Scalar256(U256::ZERO);
+    pub const ONE: Scalar256 = Scalar256(U256::ONE);
+
+    pub fn new(value: U256) -> Scalar256 {
+        Scalar256(value.wrapping_rem(&N))
+    }
+
+    // big-endian bytes of any length, reduced mod n
+    pub fn from_bytes_reduced(bytes: &[u8]) -> Scalar256 {
+        let n = BigUint::from_bytes_be(&N.to_be_bytes());
+        let reduced = (BigUint::from_bytes_be(bytes) % n).to_bytes_be();
+        let mut padded = [0u8; 32];
+        padded[32 - reduced.len()..].copy_from_slice(&reduced);
+        Scalar256(U256::from_be_bytes(padded))
+    }
+
+    pub fn value(&self) -> U256 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == U256::ZERO
+    }
+
+    // For public values (e.g. s in verification). Secret values such as
+    // a signing nonce should go through invert_ct instead.
+    pub fn invert(&self) -> Option<Scalar256> {
+        if self.is_zero() {
+            return None;
+        }
+        let params = DynResidueParams::new(&N);
+        let (inv, _) = DynResidue::new(&self.0, params).invert();
+        Some(Scalar256(inv.retrieve()))
+    }
+
+    // Fermat inversion a^(n-2) with a fixed 4-bit window. The sequence of
+    // squarings and multiplications depends only on the public n, so the
+    // timing doesn't depend on a, at the price of ~300 multiplications
+    // against a handful of gcd steps. Zero maps to zero.
+    pub fn invert_ct(&self) -> Scalar256 {
+        let params = DynResidueParams::new(&N);
+        let base = DynResidue::new(&self.0, params);
+
+        let mut table = [DynResidue::one(params); 16];
+        for i in 1..16 {
+            table[i] = table[i - 1] * base;
+        }
+
+        let mut acc = DynResidue::one(params);
+        let exp = N.wrapping_sub(&U256::from(2u8)).to_be_bytes();
+        for byte in exp {
+            for nibble in [byte >> 4, byte & 0x0f] {
+                for _ in 0..4 {
+                    acc = acc.square();
+                }
+                acc *= table[nibble as usize];
+            }
+        }
+        Scalar256(acc.retrieve())
+    }
+}
+
+impl SECP256K1 {
+    // Big-endian bytes of any width reduced mod n, e.g. a hash wider than
+    // n. For a 64-byte input the bias of the reduction is ~2^-256.
+    #[must_use]
+    pub fn reduce_to_scalar(&self, wide: &[u8]) -> U256 {
+        Scalar256::from_bytes_reduced(wide).value()
+    }
+}
+
+impl Add for Scalar256 {
+    type Output = Scalar256;
+    fn add(self, rhs: Scalar256) -> Scalar256 {
+        Scalar256(self.0.add_mod(&rhs.0, &N))
+    }
+}
+
+impl Sub for Scalar256 {
+    type Output = Scalar256;
+    fn sub(self, rhs: Scalar256) -> Scalar256 {
+        Scalar256(self.0.sub_mod(&rhs.0, &N))
+    }
+}
+
+impl Neg for Scalar256 {
+    type Output = Scalar256;
+    fn neg(self) -> Scalar256 {
+        Scalar256::ZERO - self
+    }
+}
+
+impl Mul for Scalar256 {
+    type Output = Scalar256;
+    fn mul(self, rhs: Scalar256) -> Scalar256 {
+        let params = DynResidueParams::new(&N);
+        let a = DynResidue::new(&self.0, params);
+        let b = DynResidue::new(&rhs.0, params);
+        Scalar256((a * b).retrieve())
+    }
+}
+
+impl Mul<Point<4>> for Scalar256 {
+    type Output = Point<4>;
+    fn mul(self, rhs: Point<4>) -> Point<4> {
+        self.0 * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_to_scalar_works() {
+        let secp256k1 = SECP256K1::new();
+        let wide: Vec<u8> = (0u8..64).map(|i| i.wrapping_mul(37).wrapping_add(0xa5)).collect();
+
+        let n = BigUint::parse_bytes(secp256k1.params().n.as_bytes(), 16).unwrap();
+        let expected = BigUint::from_bytes_be(&wide) % n;
+        let reduced = secp256k1.reduce_to_scalar(&wide);
+        assert_eq!(BigUint::from_bytes_be(&reduced.to_be_bytes()), expected);
+
+        // short inputs are just read as numbers
+        assert_eq!(secp256k1.reduce_to_scalar(&[1, 0]), U256::from(256u16));
+        assert_eq!(secp256k1.reduce_to_scalar(&[]), U256::ZERO);
+    }
+
+    #[test]
+    fn invert_works() {
+        let a = Scalar256::new(U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb"));
+        assert_eq!(a * a.invert().unwrap(), Scalar256::ONE);
+        assert_eq!(Scalar256::ONE.invert(), Some(Scalar256::ONE));
+        assert_eq!(Scalar256::ZERO.invert(), None);
+    }
+
+    #[test]
+    fn invert_ct_matches_invert() {
+        let values = [
+            U256::ONE,
+            U256::from(2u8),
+            U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb"),
+            N.wrapping_sub(&U256::ONE),
+        ];
+        for value in values {
+            let a = Scalar256::new(value);
+            assert_eq!(a.invert_ct(), a.invert().unwrap());
+        }
+        assert_eq!(Scalar256::ZERO.invert_ct(), Scalar256::ZERO);
+    }
+
+    #[test]
+    fn distributive_law_holds() {
+        let a = Scalar256::new(U256::from_be_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140"));
+        let b = Scalar256::new(U256::from_be_hex("8f3cbb0a8d9e7d1f2c3b4a5968778695a4b3c2d1e0f1e2d3c4b5a69788796a5b"));
+        let c = Scalar256::new(U256::from_be_hex("7e5f4552091a69125d5dfcb7b8c2659029395bdf000000000000000000000001"));
+        assert_eq!(a * (b + c), a * b + a * c);
+        assert_eq!(a * (b - c), a * b - a * c);
+    }
+
+    #[test]
+    fn wraparound_works() {
+        let n_minus_one = Scalar256::new(N.wrapping_sub(&U256::ONE));
+        let two = Scalar256::new(U256::from(2u8));
+        let three = Scalar256::new(U256::from(3u8));
+
+        // 0 - 1 = n - 1 and back
+        assert_eq!(Scalar256::ZERO - Scalar256::ONE, n_minus_one);
+        assert_eq!(n_minus_one + Scalar256::ONE, Scalar256::ZERO);
+        assert_eq!(two - three, n_minus_one);
+        assert_eq!(n_minus_one + two, Scalar256::ONE);
+
+        assert_eq!(-Scalar256::ONE, n_minus_one);
+        assert_eq!(-Scalar256::ZERO, Scalar256::ZERO);
+        assert_eq!(-(-three), three);
+        assert_eq!(three + -three, Scalar256::ZERO);
+
+        // (-1)(-1) = 1 and (-1) 2 = -2
+        assert_eq!(n_minus_one * n_minus_one, Scalar256::ONE);
+        assert_eq!(n_minus_one * two, -two);
+        assert_eq!(two * three, Scalar256::new(U256::from(6u8)));
+    }
+
+    #[test]
+    fn reduction_works() {
+        // n itself and n + 1 as bytes
+        assert_eq!(Scalar256::from_bytes_reduced(&N.to_be_bytes()), Scalar256::ZERO);
+        assert_eq!(Scalar256::new(N), Scalar256::ZERO);
+        let mut wide = [0u8; 33];
+        wide[1..].copy_from_slice(&N.to_be_bytes());
+        wide[32] += 1;
+        assert_eq!(Scalar256::from_bytes_reduced(&wide), Scalar256::ONE);
+
+        // multiplying a point agrees with U256 * Point
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        assert_eq!(Scalar256::new(U256::from(5u8)) * g, U256::from(5u8) * g);
+    }
+}
@@ -1,27 +1,82 @@
 use finite_field::FieldElementBig;
-use crate::elliptic_curves_bigint::{Coords, EllipticCurve, Point};
-use crypto_bigint::{U256, NonZero, RandomMod, rand_core::OsRng};
+use crate::elliptic_curves_bigint::{bits_msb_first, Coords, EllipticCurve, Point};
+use crate::error::{EcError, KeyError};
+use crate::jacobian::JacobianPoint;
+use crate::window::WindowTable;
+use crypto_bigint::{Encoding, U256, Limb, rand_core::{OsRng, RngCore}};
 use num_bigint::BigUint;
 use Coords::{Some};
+use std::fmt;
 
+// The constants are private so they can't drift from the parsed curve and
+// generator (and G table) cached next to them; params() hands out copies
+// as hex.
 pub struct SECP256K1 {
+    p: String,
+    gx: String,
+    gy: String,
+    n: String,
+    a: u8,
+    b: u8,    
+    // parsed once in new() so the getters don't re-read the hex
+    curve: EllipticCurve<4>,
+    generator: Point<4>,
+    // 0G..15G, shared by every fixed-base mul
+    g_table: WindowTable<4>,
+}
+
+// The curve constants as lowercase hex, for auditing or feeding a generic curve builder.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveParamsHex {
     pub p: String,
+    pub a: String,
+    pub b: String,
     pub gx: String,
     pub gy: String,
     pub n: String,
-    pub a: u8,
-    pub b: u8,    
+}
+
+impl fmt::Display for CurveParamsHex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "p:  {}", self.p)?;
+        writeln!(f, "a:  {}", self.a)?;
+        writeln!(f, "b:  {}", self.b)?;
+        writeln!(f, "Gx: {}", self.gx)?;
+        writeln!(f, "Gy: {}", self.gy)?;
+        write!(f, "n:  {}", self.n)
+    }
 }
 
 impl SECP256K1 {
     pub fn new() -> SECP256K1 {
+        let p = Self::get_p();
+        let gx = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798".to_owned();
+        let gy = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8".to_owned();
+        let n = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141".to_owned();
+        let a = 0u8;
+        let b = 7u8;
+
+        let prime = U256::from_be_hex(&p);
+        let curve = EllipticCurve {
+            a: FieldElementBig::new(U256::from(a), prime),
+            b: FieldElementBig::new(U256::from(b), prime),
+        };
+        let x = FieldElementBig::new(U256::from_be_hex(&gx), prime);
+        let y = FieldElementBig::new(U256::from_be_hex(&gy), prime);
+        let generator = Point::new(Some(x, y), curve);
+        let g_table = WindowTable::new(&generator);
+
         SECP256K1 {
-            p: Self::get_p(),
-            gx: "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798".to_owned(),
-            gy: "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8".to_owned(),
-            n: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141".to_owned(),
-            a: 0u8,
-            b: 7u8,
+            p,
+            gx,
+            gy,
+            n,
+            a,
+            b,
+            curve,
+            generator,
+            g_table,
         }
     }    
 
@@ -32,49 +87,159 @@ impl SECP256K1 {
         p.to_str_radix(16)
     }    
 
+    #[must_use]
     pub fn get_order(&self) -> U256 {
         U256::from_be_hex(&self.p)   
     }
+    #[must_use]
     pub fn get_curve(&self) -> EllipticCurve<4> {
-        let a = U256::from(self.a);
-        let b = U256::from(self.b);
-        let p = self.get_order();
-        let a = FieldElementBig::new(a, p);
-        let b = FieldElementBig::new(b, p);
-        EllipticCurve {a, b}
+        self.curve
     }
+    #[must_use]
     pub fn get_generator_coords(&self) -> Coords<4> {
-        let gx = U256::from_be_hex(self.gx.as_str());
-        let gy = U256::from_be_hex(self.gy.as_str());
-        let p = self.get_order();
-        let gx = FieldElementBig::new(gx, p);
-        let gy = FieldElementBig::new(gy, p);
-        Some(gx, gy)
+        self.generator.coords
     }   
 
+    #[must_use]
     pub fn get_generator_point(&self) -> Point<4> {
-        let curve = self.get_curve();
-        let coords = self.get_generator_coords();
-        Point::new(coords, curve)
+        self.generator
     }
 
+    // the cached window table of G, for fixed-base muls in other modules
+    pub(crate) fn g_table(&self) -> &WindowTable<4> {
+        &self.g_table
+    }
+
+    #[must_use]
     pub fn get_group_order(&self) -> U256 {
         
         U256::from_be_hex(self.n.as_str()) 
     }    
 
-    // generate a cryptographically secure random key less than n
+    #[must_use]
+    pub fn params(&self) -> CurveParamsHex {
+        CurveParamsHex {
+            p: self.p.clone(),
+            a: format!("{:064x}", self.a),
+            b: format!("{:064x}", self.b),
+            gx: self.gx.clone(),
+            gy: self.gy.clone(),
+            n: self.n.clone(),
+        }
+    }
+
+    // generate a cryptographically secure random key in [1, n)
+    #[must_use]
     pub fn get_secret_key(&self) -> U256 {
-        let n = self.get_group_order();
-        let modulus = NonZero::new(n).unwrap();
-        U256::random_mod(&mut OsRng, &modulus)    
+        random_scalar(&mut OsRng, self.get_group_order())
     }    
 
+    #[must_use]
     pub fn get_public_key(&self, secret_key: U256) -> Point<4> {
-        let point = self.get_generator_point();
-        secret_key * point    
+        // the secret key picks table entries, so use the constant-time lookup
+        self.g_table.mul_ct(secret_key)
     }    
 
+    // get_public_key for a secret that is a valid key, i.e. in [1, n)
+    pub fn try_public_key(&self, secret_key: U256) -> Result<Point<4>, KeyError> {
+        if secret_key == U256::ZERO {
+            return Err(KeyError::Zero);
+        }
+        if secret_key >= self.get_group_order() {
+            return Err(KeyError::OutOfRange);
+        }
+        Ok(self.get_public_key(secret_key))
+    }
+
+    // Recomputes what the hex constants must satisfy, to catch a miscopied
+    // digit: p = 2^256 - 2^32 - 977, b = 7, G on the curve and n G = O.
+    // Cheap enough to run once at startup.
+    pub fn self_test(&self) -> Result<(), EcError> {
+        let expected_p = U256::ZERO.wrapping_sub(&U256::from(0x1_0000_03d1u64));
+        let p = U256::from_be_hex(&self.p);
+        if p != expected_p || self.a != 0 || self.b != 7 {
+            return Err(EcError::InvalidParameters);
+        }
+        let curve = EllipticCurve {
+            a: FieldElementBig::new(U256::from(self.a), p),
+            b: FieldElementBig::new(U256::from(self.b), p),
+        };
+        if curve != self.curve {
+            return Err(EcError::InvalidParameters);
+        }
+        let x = FieldElementBig::new(U256::from_be_hex(&self.gx), p);
+        let y = FieldElementBig::new(U256::from_be_hex(&self.gy), p);
+        let generator = Point::try_new(Some(x, y), curve)?;
+        if generator != self.generator {
+            return Err(EcError::InvalidParameters);
+        }
+        if !JacobianPoint::mul_affine(U256::from_be_hex(&self.n), &generator).is_identity() {
+            return Err(EcError::InvalidParameters);
+        }
+        Ok(())
+    }
+
+    // A usable public key: built on this curve, on it, not the identity and
+    // in the subgroup generated by G. The cofactor is 1 so the last check
+    // can't fail for an on-curve point, but it's cheap insurance.
+    pub fn validate_public_key(&self, pubkey: &Point<4>) -> Result<(), EcError> {
+        if pubkey.curve != self.curve {
+            return Err(EcError::CurveMismatch);
+        }
+        if !self.curve.is_on_curve(&pubkey.coords) {
+            return Err(EcError::NotOnCurve);
+        }
+        if pubkey.is_identity() {
+            return Err(EcError::Identity);
+        }
+        if !self.is_in_subgroup(*pubkey) {
+            return Err(EcError::NotInSubgroup);
+        }
+        Ok(())
+    }
+
+    // n * p == O. The cofactor is 1, so for secp256k1 this holds for every
+    // point on the curve; it's here so callers can write the same check for
+    // every curve, and see CurveParams::is_in_subgroup for curves where it
+    // matters.
+    #[must_use]
+    pub fn is_in_subgroup(&self, p: Point<4>) -> bool {
+        JacobianPoint::mul_affine(self.get_group_order(), &p).is_identity()
+    }
+
+    // k * G for a 32-byte big-endian k, reduced mod n first, so callers
+    // don't need crypto_bigint to build the scalar
+    #[must_use]
+    pub fn mul_generator_bytes(&self, k: &[u8; 32]) -> Point<4> {
+        let k = U256::from_be_bytes(*k).wrapping_rem(&self.get_group_order());
+        self.get_public_key(k)
+    }
+
+    // Taproot-style tweaks: pubkey + tweak * G, and the matching secret
+    // (secret + tweak) mod n, so that the tweaked secret's public key is the
+    // tweaked public key. The tweak is reduced mod n.
+    #[must_use]
+    pub fn tweak_pubkey(&self, pubkey: Point<4>, tweak: U256) -> Point<4> {
+        let tweak = tweak.wrapping_rem(&self.get_group_order());
+        pubkey + self.g_table.mul_vartime(tweak)
+    }
+
+    #[must_use]
+    pub fn tweak_secret(&self, secret_key: U256, tweak: U256) -> U256 {
+        let n = self.get_group_order();
+        secret_key.wrapping_rem(&n).add_mod(&tweak.wrapping_rem(&n), &n)
+    }
+
+    // parent + delta * G, the child key of stealth addresses and non-hardened
+    // BIP32 steps. Since (a + b) G = aG + bG, anyone holding the parent
+    // public key derives it, while only the parent secret's holder knows
+    // its secret, tweak_secret(parent_secret, delta).
+    #[must_use]
+    pub fn derive_additive_child(&self, parent_pub: Point<4>, delta: U256) -> Point<4> {
+        self.tweak_pubkey(parent_pub, delta)
+    }
+
+    #[must_use]
     pub fn get_pubkey_str(&self, secret_key: U256) -> String {
         let public = self.get_public_key(secret_key);
         if let Some(x, y) =  public.coords {
@@ -83,6 +248,62 @@ impl SECP256K1 {
             "ZERO".to_owned()    
         }
     }    
+
+    // A uniformly random non-identity point of the subgroup, as k * G for
+    // a random k in [1, n), for tests that need valid points but no secret.
+    // k is thrown away, and the mul is variable time.
+    #[must_use]
+    pub fn random_point(&self, rng: &mut impl RngCore) -> Point<4> {
+        let k = random_scalar(rng, self.get_group_order());
+        self.g_table.mul_vartime(k)
+    }
+
+    // Computes (scalar + r * n) * point for a random 32-bit r. Since n * point
+    // is the identity this equals scalar * point, but the bit pattern walked
+    // by the double-and-add differs on every call. This is defense in depth
+    // against power analysis, not a constant-time guarantee.
+    #[must_use]
+    pub fn mul_blinded(&self, scalar: U256, point: Point<4>, rng: &mut impl RngCore) -> Point<4> {
+        let n = self.get_group_order();
+        let r = U256::from(rng.next_u32());
+
+        // the blinded scalar is hi * 2^256 + lo, up to 289 bits wide
+        let (lo, hi) = n.mul_wide(&r);
+        let (lo, carry) = lo.adc(&scalar, Limb::ZERO);
+        let hi = hi.wrapping_add(&U256::from_word(carry.0));
+
+        let mut result = JacobianPoint::identity(point.curve);
+        for bit in bits_msb_first(hi) {
+            result = result.double();
+            if bit {
+                result = result.add_affine(&point);
+            }
+        }
+        for i in (0..U256::BITS).rev() {
+            result = result.double();
+            if lo.bit_vartime(i) {
+                result = result.add_affine(&point);
+            }
+        }
+        result.to_affine()
+    }
+}
+
+// A uniform scalar in [1, n), by rejection sampling. Draws are masked to
+// the bit length of n, so at least half of them land below n whatever its
+// size. Random keys and points throughout the crate come from here.
+// Panics if n < 2, where [1, n) is empty and the loop would never end.
+pub(crate) fn random_scalar<R: RngCore + ?Sized>(rng: &mut R, n: U256) -> U256 {
+    assert!(n >= U256::from(2u8), "the order must be at least 2");
+    let mask = U256::MAX >> (256 - n.bits_vartime());
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        let scalar = U256::from_be_bytes(bytes) & mask;
+        if scalar != U256::ZERO && scalar < n {
+            return scalar;
+        }
+    }
 }
 
 
@@ -112,6 +333,349 @@ mod tests {
         assert_eq!(zero, group_order * point);
     }
 
+    fn point_from_hex(secp256k1: &SECP256K1, x: &str, y: &str) -> Point<4> {
+        let p = secp256k1.get_order();
+        let x = FieldElementBig::new(U256::from_be_hex(x), p);
+        let y = FieldElementBig::new(U256::from_be_hex(y), p);
+        Point::new(Some(x, y), secp256k1.get_curve())
+    }
+
+    #[test]
+    fn secp256k1_group_law_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+
+        // 2G
+        let g2 = point_from_hex(
+            &secp256k1,
+            "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+            "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+        );
+        // 3G
+        let g3 = point_from_hex(
+            &secp256k1,
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+        );
+
+        // doubling
+        assert_eq!(g + g, g2);
+
+        // adding two distinct points
+        assert_eq!(g + g2, g3);
+        assert_eq!(g2 + g, g3);
+
+        // adding a point to its inverse (x, p - y)
+        let p = secp256k1.get_order();
+        let gy = U256::from_be_hex(&secp256k1.gy);
+        let neg_g = point_from_hex(&secp256k1, &secp256k1.gx, &format!("{:x}", p.wrapping_sub(&gy)));
+        assert_eq!(g + neg_g, Point::new(Identity, secp256k1.get_curve()));
+    }
+
+    #[test]
+    fn random_point_works() {
+        let secp256k1 = SECP256K1::new();
+        let points: Vec<Point<4>> = (0..100).map(|_| secp256k1.random_point(&mut OsRng)).collect();
+        for point in &points {
+            assert!(secp256k1.get_curve().contains(point));
+            assert!(!point.is_identity());
+        }
+        assert_ne!(points[0], points[1]);
+    }
+
+    #[test]
+    fn random_scalar_works_for_any_order() {
+        for n in [U256::from(2u8), U256::from(21u8), U256::ONE.shl_vartime(200), SECP256K1::new().get_group_order()] {
+            for _ in 0..20 {
+                let k = random_scalar(&mut OsRng, n);
+                assert!(k != U256::ZERO && k < n);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 2")]
+    fn random_scalar_rejects_an_order_below_two() {
+        let _ = random_scalar(&mut OsRng, U256::ONE);
+    }
+
+    #[test]
+    fn mul_blinded_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let k = U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb");
+        let expected = k * g;
+        for _ in 0..4 {
+            assert_eq!(secp256k1.mul_blinded(k, g, &mut OsRng), expected);
+        }
+
+        // also for a point other than the generator
+        let p = U256::from(7u8) * g;
+        assert_eq!(secp256k1.mul_blinded(k, p, &mut OsRng), k * p);
+    }
+
+    #[test]
+    fn secp256k1_wraparound_works() {
+        let secp256k1 = SECP256K1::new();
+        let p = secp256k1.get_order();
+        let g = secp256k1.get_generator_point();
+        let g2 = g + g;
+
+        // 3G, 4G, 5G, 7G
+        let g3 = point_from_hex(
+            &secp256k1,
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+        );
+        let g4 = point_from_hex(
+            &secp256k1,
+            "e493dbf1c10d80f3581e4904930b1404cc6c13900ee0758474fa94abe8c4cd13",
+            "51ed993ea0d455b75642e2098ea51448d967ae33bfbdfe40cfe97bdc47739922",
+        );
+        let g5 = point_from_hex(
+            &secp256k1,
+            "2f8bde4d1a07209355b4a7250a5c5128e88b84bddc619ab7cba8d569b240efe4",
+            "d8ac222636e5e3d6d4dba9dda6c9c426f788271bab0d6840dca87d3aa6ac62d6",
+        );
+        let g7 = point_from_hex(
+            &secp256k1,
+            "5cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc",
+            "6aebca40ba255960a3178d6d861a54dba813d0b813fde7b5a5082628087264da",
+        );
+
+        // x(4G) < x(3G) and y(3G) < y(4G): both differences in the slope
+        // underflow before they are reduced mod p
+        assert_eq!(g4 + g3, g7);
+        assert_eq!(g3 + g4, g7);
+        assert_eq!(g3 + g2, g5);
+
+        // -2G has y = p - y(2G), close to p
+        let Some(x2, y2) = g2.coords else { unreachable!() };
+        let neg_g2 = point_from_hex(
+            &secp256k1,
+            &format!("{:x}", x2.get_num()),
+            &format!("{:x}", p.wrapping_sub(&y2.get_num())),
+        );
+        assert_eq!(g3 + neg_g2, g);
+        assert_eq!(neg_g2 + g7, g5);
+    }
+
+    #[test]
+    fn secp256k1_mul_consistency_works() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+        let g = secp256k1.get_generator_point();
+        let two = U256::from(2u8);
+
+        let g3 = point_from_hex(
+            &secp256k1,
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+        );
+        let g4 = point_from_hex(
+            &secp256k1,
+            "e493dbf1c10d80f3581e4904930b1404cc6c13900ee0758474fa94abe8c4cd13",
+            "51ed993ea0d455b75642e2098ea51448d967ae33bfbdfe40cfe97bdc47739922",
+        );
+
+        // 2 (2G) = 4G, through the scalar mul and through plain doubling
+        assert_eq!(two * (two * g), g4);
+        assert_eq!(g.double().double(), g4);
+        assert_eq!(U256::from(4u8) * g, g4);
+
+        // 3G = G + G + G
+        assert_eq!(g + g + g, g3);
+        assert_eq!(U256::from(3u8) * g, g3);
+
+        // aG + bG = (a + b mod n) G, with the expected sum from a reference
+        // implementation; the second pair sums to -3 mod n
+        let cases = [
+            (
+                "e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb",
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+                "a5170d8f4ed66b9606ae872d41564536ab9f598b1b341ef52b538b914b05be09",
+                "cea4541a9881636e0b3c357a7e29040a3eefe9a62831408070420828a401e249",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd036413f",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "c77084f09cd217ebf01cc819d5c80ca99aff5666cb3ddce4934602897b4715bd",
+            ),
+            (
+                "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0",
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+                "12f184d647ddd52ca485c118935c41cdc81e85b2a51a1e18c3b2e7b074b98a43",
+                "efca02149068e5e93d30cde8bb1896627ac32bbc46477eb9b6b5f05749314198",
+            ),
+        ];
+        for (a, b, x, y) in cases {
+            let a = U256::from_be_hex(a);
+            let b = U256::from_be_hex(b);
+            let expected = point_from_hex(&secp256k1, x, y);
+            assert_eq!(a * g + b * g, expected);
+            assert_eq!(a.add_mod(&b, &n) * g, expected);
+        }
+    }
+
+    #[test]
+    fn params_works() {
+        let secp256k1 = SECP256K1::new();
+        let params = secp256k1.params();
+
+        // p = 2^256 - 2^32 - 977
+        let n2 = BigUint::from(2u8);
+        let p = n2.pow(256) - n2.pow(32) - BigUint::from(977u16);
+        assert_eq!(BigUint::parse_bytes(params.p.as_bytes(), 16).unwrap(), p);
+
+        assert_eq!(params.a, "0".repeat(64));
+        assert_eq!(params.b, format!("{}7", "0".repeat(63)));
+        assert!(params.to_string().contains(&format!("Gx: {}", secp256k1.gx)));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn params_serialize_to_json() {
+        let params = SECP256K1::new().params();
+        let json = serde_json::to_string(&params).unwrap();
+        assert!(json.starts_with(&format!("{{\"p\":\"{}\",\"a\":\"", params.p)));
+        assert!(json.contains(&format!("\"gx\":\"{}\"", params.gx)));
+        assert!(json.contains(&format!("\"n\":\"{}\"", params.n)));
+        assert_eq!(serde_json::from_str::<CurveParamsHex>(&json).unwrap(), params);
+    }
+
+    #[test]
+    fn generator_is_cached() {
+        let secp256k1 = SECP256K1::new();
+        assert_eq!(secp256k1.get_generator_point(), secp256k1.get_generator_point());
+
+        // the cached point matches one parsed from the hex constants
+        let parsed = point_from_hex(&secp256k1, &secp256k1.gx, &secp256k1.gy);
+        assert_eq!(secp256k1.get_generator_point(), parsed);
+        assert_eq!(secp256k1.get_generator_coords(), parsed.coords);
+        assert_eq!(secp256k1.get_curve(), parsed.curve);
+    }
+
+    #[test]
+    fn mul_generator_bytes_works() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+
+        let mut k = [0u8; 32];
+        k[29..].copy_from_slice(&[0x01, 0xe2, 0x40]);
+        assert_eq!(secp256k1.mul_generator_bytes(&k), U256::from(123456u32) * g);
+
+        // n + 1 reduces to 1
+        let n_plus_one = secp256k1.get_group_order().wrapping_add(&U256::ONE);
+        assert_eq!(secp256k1.mul_generator_bytes(&n_plus_one.to_be_bytes()), g);
+    }
+
+    #[test]
+    fn tweaks_are_consistent() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+        for _ in 0..4 {
+            let secret = secp256k1.get_secret_key();
+            let tweak = secp256k1.get_secret_key();
+            let tweaked = secp256k1.tweak_secret(secret, tweak);
+            assert_eq!(
+                secp256k1.get_public_key(tweaked),
+                secp256k1.tweak_pubkey(secp256k1.get_public_key(secret), tweak)
+            );
+        }
+
+        // 5 + (n - 2) wraps to 3
+        let tweak = n.wrapping_sub(&U256::from(2u8));
+        assert_eq!(secp256k1.tweak_secret(U256::from(5u8), tweak), U256::from(3u8));
+        let pubkey = secp256k1.get_public_key(U256::from(5u8));
+        assert_eq!(secp256k1.tweak_pubkey(pubkey, tweak), secp256k1.get_public_key(U256::from(3u8)));
+
+        // a zero tweak changes nothing
+        assert_eq!(secp256k1.tweak_pubkey(pubkey, U256::ZERO), pubkey);
+        assert_eq!(secp256k1.tweak_secret(U256::from(5u8), n), U256::from(5u8));
+    }
+
+    #[test]
+    fn derive_additive_child_works() {
+        let secp256k1 = SECP256K1::new();
+        let parent_secret = secp256k1.get_secret_key();
+        let parent_pub = secp256k1.get_public_key(parent_secret);
+
+        for delta in [U256::ONE, U256::from(0xdeadbeefu32), secp256k1.get_secret_key()] {
+            let child = secp256k1.derive_additive_child(parent_pub, delta);
+            let child_secret = parent_secret.add_mod(&delta, &secp256k1.get_group_order());
+            assert_eq!(child, secp256k1.get_public_key(child_secret));
+            assert_ne!(child, parent_pub);
+        }
+    }
+
+    #[test]
+    fn is_in_subgroup_works() {
+        let secp256k1 = SECP256K1::new();
+        for _ in 0..4 {
+            assert!(secp256k1.is_in_subgroup(secp256k1.get_public_key(secp256k1.get_secret_key())));
+        }
+        assert!(secp256k1.is_in_subgroup(secp256k1.get_generator_point()));
+        assert!(secp256k1.is_in_subgroup(Point::new(Coords::Identity, secp256k1.get_curve())));
+    }
+
+    #[test]
+    fn try_public_key_works() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+
+        assert_eq!(secp256k1.try_public_key(U256::ZERO), Err(KeyError::Zero));
+        assert_eq!(secp256k1.try_public_key(n), Err(KeyError::OutOfRange));
+        assert_eq!(secp256k1.try_public_key(U256::MAX), Err(KeyError::OutOfRange));
+
+        assert_eq!(secp256k1.try_public_key(U256::ONE), Ok(secp256k1.get_generator_point()));
+        let last = n.wrapping_sub(&U256::ONE);
+        assert_eq!(secp256k1.try_public_key(last), Ok(secp256k1.get_public_key(last)));
+    }
+
+    #[test]
+    fn self_test_works() {
+        assert_eq!(SECP256K1::new().self_test(), Ok(()));
+
+        // one wrong digit in each constant
+        let corrupt = |digits: &str| {
+            let mut digits = digits.to_owned();
+            let last = if digits.ends_with('0') { "1" } else { "0" };
+            digits.replace_range(digits.len() - 1.., last);
+            digits
+        };
+        let secp256k1 = SECP256K1::new();
+        let bad_p = SECP256K1 { p: corrupt(&secp256k1.p), ..SECP256K1::new() };
+        assert_eq!(bad_p.self_test(), Err(EcError::InvalidParameters));
+        let bad_gx = SECP256K1 { gx: corrupt(&secp256k1.gx), ..SECP256K1::new() };
+        assert_eq!(bad_gx.self_test(), Err(EcError::NotOnCurve));
+        let bad_n = SECP256K1 { n: corrupt(&secp256k1.n), ..SECP256K1::new() };
+        assert_eq!(bad_n.self_test(), Err(EcError::InvalidParameters));
+        let bad_b = SECP256K1 { b: 5, ..SECP256K1::new() };
+        assert_eq!(bad_b.self_test(), Err(EcError::InvalidParameters));
+    }
+
+    #[test]
+    fn validate_public_key_works() {
+        let secp256k1 = SECP256K1::new();
+        let curve = secp256k1.get_curve();
+        let g = secp256k1.get_generator_point();
+        assert_eq!(secp256k1.validate_public_key(&g), Ok(()));
+
+        let zero = Point::new(Identity, curve);
+        assert_eq!(secp256k1.validate_public_key(&zero), Err(EcError::Identity));
+
+        // (1, 1): 1 != 1 + 7
+        let one = FieldElementBig::new(U256::ONE, secp256k1.get_order());
+        let off_curve = Point { coords: Some(one, one), curve };
+        assert_eq!(secp256k1.validate_public_key(&off_curve), Err(EcError::NotOnCurve));
+
+        // with the cofactor 1 every curve point is in the subgroup, so fake
+        // a wrong order: (n - 1) * G is -G, not the identity
+        let wrong_order = SECP256K1 { n: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140".to_owned(), ..SECP256K1::new() };
+        assert_eq!(wrong_order.validate_public_key(&g), Err(EcError::NotInSubgroup));
+    }
+
     #[test]
     fn secret_key_works() {
         let secp256k1 = SECP256K1::new();    
@@ -0,0 +1,102 @@
+// The simplified Shallue–van de Woestijne–Ulas map of RFC 9380 (section
+// 6.6.2): any field element u to a point of y^2 = x^3 + ax + b. It needs
+// a != 0 and b != 0, so it applies directly to P-256 but not to
+// secp256k1 (a = 0), which RFC 9380 handles by mapping to an isogenous
+// curve first; that variant isn't implemented here. Both curves have
+// cofactor 1, so no cofactor clearing is needed afterwards.
+use finite_field::FieldElementBig;
+use crate::elliptic_curves_bigint::{Coords, EllipticCurve, Point};
+use crate::field_ext::{FieldNeg, PowU32, Sqrt};
+use crypto_bigint::{Uint, U256};
+
+// sgn0 for a prime field: the parity of the canonical representative
+fn sgn0<const LIMBS: usize>(e: &FieldElementBig<LIMBS>) -> bool {
+    e.get_num().bit_vartime(0)
+}
+
+impl<const LIMBS: usize> EllipticCurve<LIMBS> {
+    // The map with the curve's SWU constant z: a non-square with
+    // g(b / (z a)) square, among the other RFC 9380 conditions.
+    // Variable time in u.
+    pub fn map_to_curve_with_z(&self, u: FieldElementBig<LIMBS>, z: FieldElementBig<LIMBS>) -> Point<LIMBS> {
+        let (a, b) = (self.a, self.b);
+        let p = a.get_prime();
+        let zero = FieldElementBig::new(Uint::ZERO, p);
+        let one = FieldElementBig::new(Uint::ONE, p);
+        assert!(a != zero && b != zero, "simplified SWU needs a != 0 and b != 0");
+        let g = |x: FieldElementBig<LIMBS>| x.pow_u32(3) + a * x + b;
+
+        // x1 = -b/a (1 + 1/(z^2 u^4 + z u^2)), or b/(z a) when that inverse is 0
+        let zu2 = z * u * u;
+        let denominator = zu2 * zu2 + zu2;
+        let x1 = if denominator == zero {
+            b / (z * a)
+        } else {
+            (b / a).neg() * (one + one / denominator)
+        };
+        // when g(x1) isn't square, g(z u^2 x1) is
+        let (x, y) = match g(x1).sqrt() {
+            Some(y) => (x1, y),
+            None => {
+                let x2 = zu2 * x1;
+                (x2, g(x2).sqrt().expect("z is not a valid SWU constant for this curve"))
+            }
+        };
+        let y = if sgn0(&u) == sgn0(&y) { y } else { y.neg() };
+        Point::new(Coords::Some(x, y), *self)
+    }
+}
+
+impl EllipticCurve<4> {
+    // The map with z = -10, the RFC 9380 constant for P-256. Other curves
+    // need their own z, via map_to_curve_with_z.
+    pub fn map_to_curve(&self, u: FieldElementBig<4>) -> Point<4> {
+        let p = self.a.get_prime();
+        let z = FieldElementBig::new(p.wrapping_sub(&U256::from(10u8)), p);
+        self.map_to_curve_with_z(u, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_to_curve_works() {
+        let p256 = EllipticCurve::from_name("P-256").unwrap().curve;
+        let p = p256.a.get_prime();
+        let u = |hex: &str| FieldElementBig::new(U256::from_be_hex(hex), p);
+
+        // RFC 9380 J.1.1, P256_XMD:SHA-256_SSWU_NU_ with msg = "": u[0] -> Q
+        let q = p256.map_to_curve(u("b22d487045f80e9edcb0ecc8d4bf77833e2bf1f3a54004d7df1d57f4802d311f"));
+        assert_eq!(q, Point::new(Coords::Some(
+            u("f871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1"),
+            u("87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b"),
+        ), p256));
+
+        for n in [0u32, 1, 2, 3, 12345, 0xffff_ffff] {
+            let u = FieldElementBig::new(U256::from(n), p);
+            let point = p256.map_to_curve(u);
+            assert!(p256.contains(&point), "u = {}", n);
+
+            // the sign of y follows the sign of u, so -u maps to -point
+            if let Coords::Some(_, y) = point.coords {
+                assert_eq!(sgn0(&y), sgn0(&u));
+            }
+            if n != 0 {
+                assert_eq!(p256.map_to_curve(u.neg()), Point::new(match point.coords {
+                    Coords::Some(x, y) => Coords::Some(x, y.neg()),
+                    Coords::Identity => Coords::Identity,
+                }, p256));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_to_curve_rejects_a_zero() {
+        let secp256k1 = crate::secp256k1::SECP256K1::new().get_curve();
+        let p = secp256k1.a.get_prime();
+        let _ = secp256k1.map_to_curve(FieldElementBig::new(U256::from(5u8), p));
+    }
+}
@@ -0,0 +1,129 @@
+use finite_field::FieldElementBig;
+use crate::elliptic_curves_bigint::Point;
+use crate::jacobian::JacobianPoint;
+use crypto_bigint::Uint;
+use crypto_bigint::subtle::{ConditionallySelectable, ConstantTimeEq};
+
+const WINDOW: usize = 4;
+
+// 0P, 1P, ..., 15P for fixed 4-bit window scalar mul. There are two ways
+// to read it:
+// - lookup_ct touches every entry and keeps the wanted one with a
+//   constant-time select, so the memory access pattern doesn't leak the
+//   digit. mul_ct uses it; get_public_key goes through mul_ct because its
+//   scalar is a secret key.
+// - lookup_vartime is a plain index for public scalars, e.g. the u1, u2
+//   of signature verification; mul_vartime uses it.
+// Only the lookup is constant time: Jacobian addition still branches on
+// the identity.
+pub struct WindowTable<const LIMBS: usize> {
+    points: Vec<JacobianPoint<LIMBS>>,
+}
+
+impl<const LIMBS: usize> WindowTable<LIMBS> {
+    pub fn new(point: &Point<LIMBS>) -> WindowTable<LIMBS> {
+        let mut points = vec![JacobianPoint::identity(point.curve)];
+        for i in 1..1 << WINDOW {
+            points.push(points[i - 1].add_affine(point));
+        }
+        WindowTable { points }
+    }
+
+    // digit * P; panics if digit is 16 or more
+    pub fn lookup_vartime(&self, digit: usize) -> JacobianPoint<LIMBS> {
+        self.points[digit]
+    }
+
+    // digit * P without a digit-dependent index; digits of 16 or more
+    // match no entry and give the identity
+    pub fn lookup_ct(&self, digit: u8) -> JacobianPoint<LIMBS> {
+        let first = self.points[0];
+        let (mut x, mut y, mut z) = (first.x.get_num(), first.y.get_num(), first.z.get_num());
+        for (i, entry) in self.points.iter().enumerate().skip(1) {
+            let hit = (i as u8).ct_eq(&digit);
+            x = Uint::conditional_select(&x, &entry.x.get_num(), hit);
+            y = Uint::conditional_select(&y, &entry.y.get_num(), hit);
+            z = Uint::conditional_select(&z, &entry.z.get_num(), hit);
+        }
+        let p = first.curve.a.get_prime();
+        JacobianPoint {
+            x: FieldElementBig::new(x, p),
+            y: FieldElementBig::new(y, p),
+            z: FieldElementBig::new(z, p),
+            curve: first.curve,
+        }
+    }
+
+    // every window of the full width, including leading zero windows
+    pub fn mul_ct(&self, scalar: Uint<LIMBS>) -> Point<LIMBS> {
+        let mut result = self.points[0];
+        for w in (0..Uint::<LIMBS>::BITS / WINDOW).rev() {
+            for _ in 0..WINDOW {
+                result = result.double();
+            }
+            result = result.add(&self.lookup_ct(window_digit(&scalar, w) as u8));
+        }
+        result.to_affine()
+    }
+
+    pub fn mul_vartime(&self, scalar: Uint<LIMBS>) -> Point<LIMBS> {
+        let mut result = self.points[0];
+        for w in (0..scalar.bits_vartime().div_ceil(WINDOW)).rev() {
+            for _ in 0..WINDOW {
+                result = result.double();
+            }
+            let digit = window_digit(&scalar, w);
+            if digit != 0 {
+                result = result.add(&self.lookup_vartime(digit));
+            }
+        }
+        result.to_affine()
+    }
+}
+
+// bits [WINDOW w, WINDOW (w + 1)) of scalar
+fn window_digit<const LIMBS: usize>(scalar: &Uint<LIMBS>, w: usize) -> usize {
+    (0..WINDOW).fold(0, |digit, i| digit | ((scalar.bit_vartime(w * WINDOW + i) as usize) << i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::SECP256K1;
+    use crypto_bigint::U256;
+
+    #[test]
+    fn lookups_agree() {
+        let secp256k1 = SECP256K1::new();
+        let g = secp256k1.get_generator_point();
+        let table = WindowTable::new(&g);
+
+        for digit in 0..16u8 {
+            let expected = U256::from(digit) * g;
+            assert_eq!(table.lookup_vartime(digit as usize).to_affine(), expected);
+            assert_eq!(table.lookup_ct(digit).to_affine(), expected);
+        }
+        assert!(table.lookup_ct(16).is_identity());
+    }
+
+    #[test]
+    fn window_muls_work() {
+        let secp256k1 = SECP256K1::new();
+        let n = secp256k1.get_group_order();
+        let point = secp256k1.get_public_key(U256::from(777u16));
+        let table = WindowTable::new(&point);
+
+        for k in [
+            U256::ZERO,
+            U256::ONE,
+            U256::from(0xf0u8),
+            U256::from_be_hex("e32868331fa8ef0138de0de85478346aec5e3912b6029ae71691c384237a3eeb"),
+            n.wrapping_sub(&U256::ONE),
+        ] {
+            let expected = k * point;
+            assert_eq!(table.mul_ct(k), expected);
+            assert_eq!(table.mul_vartime(k), expected);
+        }
+        assert!(table.mul_ct(n).is_identity());
+    }
+}
@@ -0,0 +1,70 @@
+use crypto_bigint::U256;
+use elliptic_curve::elliptic_curves::{self, Scalar};
+use elliptic_curve::elliptic_curves_bigint;
+use finite_field::FieldElement;
+
+// The generic Point<u16> and the bigint Point<4> implement the same group
+// law separately. Run the same additions, doublings and scalar muls on the
+// y^2 = x^3 + 7 curve over F_223 in both and compare, by widening every
+// generic result with From.
+type Small = elliptic_curves::Point<u16>;
+type Big = elliptic_curves_bigint::Point<4>;
+
+fn points() -> Vec<Small> {
+    let a: FieldElement<u16> = FieldElement::new(0, 223);
+    let b: FieldElement<u16> = FieldElement::new(7, 223);
+    let curve = elliptic_curves::EllipticCurve::new(a, b);
+    let point = |x: u16, y: u16| {
+        elliptic_curves::Point::new(elliptic_curves::Coords::Some(FieldElement::new(x, 223), FieldElement::new(y, 223)), curve)
+    };
+    vec![
+        point(192, 105),
+        point(17, 56),
+        point(1, 193),
+        point(47, 71),
+        point(143, 98),
+        point(76, 66),
+        elliptic_curves::Point::new(elliptic_curves::Coords::Identity, curve),
+    ]
+}
+
+#[test]
+fn conversion_keeps_the_curve_and_coordinates() {
+    for small in points() {
+        let big: Big = small.into();
+        assert!(big.curve.contains(&big));
+        assert_eq!(big.is_identity(), small.is_identity());
+    }
+}
+
+#[test]
+fn additions_agree() {
+    let points = points();
+    for p in &points {
+        for q in &points {
+            let big_p: Big = (*p).into();
+            let big_q: Big = (*q).into();
+            assert_eq!(Big::from(*p + *q), big_p + big_q, "{:?} + {:?}", big_p, big_q);
+        }
+    }
+}
+
+#[test]
+fn doublings_agree() {
+    for p in points() {
+        let big: Big = p.into();
+        assert_eq!(Big::from(p + p), big.double(), "2 * {:?}", big);
+        assert_eq!(Big::from(p + p + p), big.double() + big, "3 * {:?}", big);
+    }
+}
+
+#[test]
+fn scalar_muls_agree() {
+    for p in points() {
+        let big: Big = p.into();
+        // past the order 21 of (47, 71) and the group's 252 points
+        for k in (0u16..50).chain([251, 252, 253, 1000, u16::MAX]) {
+            assert_eq!(Big::from(Scalar::new(k) * p), U256::from(k) * big, "{} * {:?}", k, big);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use elliptic_curve::elliptic_curves::{Coords, EllipticCurve, Point, Scalar};
+use finite_field::FieldElement;
+
+#[test]
+fn scalar_is_usable_downstream() {
+    let a: FieldElement<u16> = FieldElement::new(0, 223);
+    let b: FieldElement<u16> = FieldElement::new(7, 223);
+    let curve = EllipticCurve::new(a, b);
+    let x: FieldElement<u16> = FieldElement::new(47, 223);
+    let y: FieldElement<u16> = FieldElement::new(71, 223);
+    let point = Point::new(Coords::Some(x, y), curve);
+
+    let scalar = Scalar::new(21u16);
+    assert_eq!(scalar.value(), 21);
+
+    // (47, 71) has order 21
+    assert!((scalar * point).is_identity());
+    assert!(!(Scalar::new(20u16) * point).is_identity());
+}
@@ -0,0 +1,112 @@
+use crypto_bigint::{Encoding, U256, rand_core::OsRng};
+use elliptic_curve::elliptic_curves_bigint::{scalar_mul_verbose, Coords, EllipticCurve, Point};
+use elliptic_curve::error::EcError;
+use elliptic_curve::jacobian::JacobianPoint;
+use elliptic_curve::msm::multi_scalar_mul;
+use elliptic_curve::scalar256::Scalar256;
+use elliptic_curve::secp256k1::SECP256K1;
+use elliptic_curve::window::WindowTable;
+
+// Every scalar multiplication in the crate is checked against this plain
+// MSB-first double-and-add on affine points. New mul variants belong in
+// all_muls_agree so they are covered too.
+fn reference_mul(scalar: U256, point: Point<4>) -> Point<4> {
+    let mut result = Point::new(Coords::Identity, point.curve);
+    for i in (0..U256::BITS).rev() {
+        result = result + result;
+        if scalar.bit_vartime(i) {
+            result = result + point;
+        }
+    }
+    result
+}
+
+fn all_muls_agree(secp256k1: &SECP256K1, scalar: U256, point: Point<4>) {
+    let expected = reference_mul(scalar, point);
+
+    assert_eq!(scalar * point, expected, "Uint * Point, k = {:?}", scalar);
+    assert_eq!(JacobianPoint::mul_affine(scalar, &point), expected, "Jacobian, k = {:?}", scalar);
+    assert_eq!(Scalar256::new(scalar) * point, expected, "Scalar256, k = {:?}", scalar);
+    assert_eq!(secp256k1.mul_blinded(scalar, point, &mut OsRng), expected, "blinded, k = {:?}", scalar);
+
+    let table = WindowTable::new(&point);
+    assert_eq!(table.mul_ct(scalar), expected, "window ct, k = {:?}", scalar);
+    assert_eq!(table.mul_vartime(scalar), expected, "window vartime, k = {:?}", scalar);
+
+    let mut log = Vec::new();
+    assert_eq!(scalar_mul_verbose(scalar, point, &mut log), expected, "verbose, k = {:?}", scalar);
+
+    assert_eq!(multi_scalar_mul(&[(scalar, point)]), expected, "msm, k = {:?}", scalar);
+    let params = EllipticCurve::from_name("secp256k1").unwrap();
+    assert_eq!(params.mul_reduced(scalar, point), expected, "mul_reduced, k = {:?}", scalar);
+
+    // mul_small only takes scalars that fit a u64
+    if scalar.bits_vartime() <= 64 {
+        let small = u64::from_be_bytes(scalar.to_be_bytes()[24..].try_into().unwrap());
+        assert_eq!(point.mul_small(small), expected, "mul_small, k = {:?}", scalar);
+    }
+
+    // the ladder sees x alone, so it can only be checked on x, and it has
+    // no encoding for the identity as input or output
+    if let Coords::Some(x, _) = point.coords {
+        let xonly = secp256k1.ecdh_xonly(scalar, x.get_num().to_be_bytes());
+        match expected.coords {
+            Coords::Some(x, _) => assert_eq!(xonly, Ok(x.get_num().to_be_bytes()), "xonly, k = {:?}", scalar),
+            Coords::Identity => assert_eq!(xonly, Err(EcError::Identity), "xonly, k = {:?}", scalar),
+        }
+    }
+}
+
+fn generator_muls_agree(secp256k1: &SECP256K1, scalar: U256) {
+    let expected = reference_mul(scalar, secp256k1.get_generator_point());
+
+    assert_eq!(secp256k1.get_public_key(scalar), expected, "get_public_key, k = {:?}", scalar);
+    assert_eq!(secp256k1.mul_generator_bytes(&scalar.to_be_bytes()), expected, "mul_generator_bytes, k = {:?}", scalar);
+}
+
+fn edge_scalars(secp256k1: &SECP256K1) -> Vec<U256> {
+    let n = secp256k1.get_group_order();
+    vec![
+        U256::ZERO,
+        U256::ONE,
+        U256::from(2u8),
+        U256::from(3u8),
+        n.wrapping_sub(&U256::ONE),
+        n.wrapping_sub(&U256::from(2u8)),
+    ]
+}
+
+#[test]
+fn muls_agree_on_edge_scalars() {
+    let secp256k1 = SECP256K1::new();
+    let g = secp256k1.get_generator_point();
+    let point = secp256k1.get_public_key(secp256k1.get_secret_key());
+
+    for scalar in edge_scalars(&secp256k1) {
+        all_muls_agree(&secp256k1, scalar, g);
+        all_muls_agree(&secp256k1, scalar, point);
+        generator_muls_agree(&secp256k1, scalar);
+    }
+}
+
+#[test]
+fn muls_agree_on_random_inputs() {
+    let secp256k1 = SECP256K1::new();
+
+    for _ in 0..8 {
+        let point = secp256k1.random_point(&mut OsRng);
+        let scalar = secp256k1.get_secret_key();
+
+        all_muls_agree(&secp256k1, scalar, point);
+        generator_muls_agree(&secp256k1, scalar);
+    }
+}
+
+#[test]
+fn muls_agree_on_the_identity() {
+    let secp256k1 = SECP256K1::new();
+    let zero = Point::new(Coords::Identity, secp256k1.get_curve());
+
+    all_muls_agree(&secp256k1, secp256k1.get_secret_key(), zero);
+    all_muls_agree(&secp256k1, U256::ZERO, zero);
+}
@@ -0,0 +1,142 @@
+use crypto_bigint::{Encoding, U256};
+use elliptic_curve::ecdsa::{signature_from_compact, signature_to_compact};
+use elliptic_curve::facade::CurveApi;
+use elliptic_curve::hash::{sha256, Sha256Hasher};
+use elliptic_curve::secp256k1::SECP256K1;
+use serde_json::Value;
+
+// Runs every entry of tests/vectors/secp256k1.json through key derivation,
+// encoding, addresses, signing and verification. Each entry has:
+//
+//   secret             32-byte secret key, hex
+//   pubkey_compressed  33-byte SEC1 public key, hex
+//   pubkey_hash160     ripemd160(sha256(pubkey_compressed)), hex
+//   address            the P2WPKH bech32 address with hrp "bc"
+//   message            UTF-8 text; the signed hash is its SHA-256
+//   signature          64-byte r || s, RFC 6979 nonce, low s, hex
+//
+// New vectors are a data change: append an entry to the file.
+const VECTORS: &str = include_str!("vectors/secp256k1.json");
+
+struct Vector {
+    secret: [u8; 32],
+    pubkey_compressed: [u8; 33],
+    pubkey_hash160: [u8; 20],
+    address: String,
+    message: String,
+    signature: [u8; 64],
+}
+
+fn hex<const N: usize>(entry: &Value, field: &str) -> [u8; N] {
+    let hex = entry[field].as_str().unwrap_or_else(|| panic!("missing string field {}", field));
+    assert_eq!(hex.len(), 2 * N, "{} should be {} bytes", field, N);
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap_or_else(|_| panic!("{} is not hex", field));
+    }
+    bytes
+}
+
+fn text(entry: &Value, field: &str) -> String {
+    entry[field].as_str().unwrap_or_else(|| panic!("missing string field {}", field)).to_owned()
+}
+
+fn vectors() -> Vec<Vector> {
+    let parsed: Value = serde_json::from_str(VECTORS).expect("vectors file is valid JSON");
+    let entries = parsed.as_array().expect("vectors file is a JSON array");
+    entries
+        .iter()
+        .map(|entry| Vector {
+            secret: hex(entry, "secret"),
+            pubkey_compressed: hex(entry, "pubkey_compressed"),
+            pubkey_hash160: hex(entry, "pubkey_hash160"),
+            address: text(entry, "address"),
+            message: text(entry, "message"),
+            signature: hex(entry, "signature"),
+        })
+        .collect()
+}
+
+#[test]
+fn vectors_file_is_not_empty() {
+    assert!(vectors().len() >= 5);
+}
+
+#[test]
+fn public_keys_match() {
+    let secp256k1 = SECP256K1::new();
+    let api = CurveApi::secp256k1();
+    for v in vectors() {
+        let secret = U256::from_be_bytes(v.secret);
+        let public = secp256k1.get_public_key(secret);
+        assert_eq!(public.to_sec_compressed(), Ok(v.pubkey_compressed), "secret {:?}", secret);
+        assert_eq!(api.public_key(&v.secret), Ok(v.pubkey_compressed), "secret {:?}", secret);
+
+        // and the encoding parses back to the same point
+        assert_eq!(secp256k1.from_sec(&v.pubkey_compressed), Ok(public));
+        assert!(secp256k1.validate_public_key(&public).is_ok());
+    }
+}
+
+#[test]
+fn addresses_match() {
+    let secp256k1 = SECP256K1::new();
+    for v in vectors() {
+        let public = secp256k1.from_sec(&v.pubkey_compressed).unwrap();
+        assert_eq!(secp256k1.pubkey_hash160(public, true), v.pubkey_hash160, "{}", v.address);
+        assert_eq!(secp256k1.to_bech32_address(public, "bc"), v.address);
+    }
+}
+
+#[test]
+fn signatures_match() {
+    let secp256k1 = SECP256K1::new();
+    let api = CurveApi::secp256k1();
+    for v in vectors() {
+        let secret = U256::from_be_bytes(v.secret);
+        let hash = sha256(&v.message);
+        let z = U256::from_be_bytes(hash);
+
+        // every signing path gives the same deterministic low-s signature
+        assert_eq!(secp256k1.sign_recoverable_compact(secret, z)[..64], v.signature[..], "message {:?}", v.message);
+        assert_eq!(api.sign(&v.secret, &hash), Ok(v.signature), "message {:?}", v.message);
+        let mut sig = secp256k1.sign_message(secret, &v.message, &Sha256Hasher);
+        if !secp256k1.is_low_s(sig.1) {
+            sig.1 = secp256k1.get_group_order().wrapping_sub(&sig.1);
+        }
+        assert_eq!(signature_to_compact(sig.0, sig.1), v.signature, "message {:?}", v.message);
+    }
+}
+
+#[test]
+fn signatures_verify() {
+    let secp256k1 = SECP256K1::new();
+    let api = CurveApi::secp256k1();
+    for v in vectors() {
+        let public = secp256k1.from_sec(&v.pubkey_compressed).unwrap();
+        let sig = signature_from_compact(&v.signature);
+        let hash = sha256(&v.message);
+
+        assert!(secp256k1.verify(public, U256::from_be_bytes(hash), sig), "message {:?}", v.message);
+        assert!(secp256k1.verify_low_s(public, U256::from_be_bytes(hash), sig));
+        assert!(secp256k1.verify_message(public, &v.message, sig, &Sha256Hasher));
+        assert!(secp256k1.verify_hash_bytes(public, hash, sig));
+        assert_eq!(api.verify(&v.pubkey_compressed, &hash, &v.signature), Ok(true));
+
+        // a different message doesn't verify
+        let other = format!("{}!", v.message);
+        assert!(!secp256k1.verify_message(public, &other, sig, &Sha256Hasher), "message {:?}", v.message);
+    }
+}
+
+#[test]
+fn signatures_recover_their_keys() {
+    let secp256k1 = SECP256K1::new();
+    for v in vectors() {
+        let secret = U256::from_be_bytes(v.secret);
+        let z = U256::from_be_bytes(sha256(&v.message));
+        let recoverable = secp256k1.sign_recoverable_compact(secret, z);
+        let recovered = secp256k1.recover_from_compact(&recoverable, z).expect("a valid signature recovers");
+        assert_eq!(recovered.to_sec_compressed(), Ok(v.pubkey_compressed));
+    }
+}